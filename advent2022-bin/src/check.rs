@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use advent2022_lib::check::{check_day, record, DayCheck, Status};
+use advent2022_lib::DayTrait;
+use color_eyre::eyre::Result;
+use colored::*;
+
+fn status_cell(status: &str) -> ColoredString {
+    let cell = format!("{:<12}", status);
+    match status {
+        "PASS" => cell.green(),
+        "RECORDED" | "UNRECORDED" | "SKIPPED" => cell.yellow(),
+        _ => cell.red(),
+    }
+}
+
+/// Check every day's real-input answers against the recorded ones, printing a table.
+///
+/// With `record_answers`, the answers of solved parts with nothing recorded are written as the
+/// recorded answers, but a wrong answer is never recorded over the right one. Days without an
+/// input are skipped. Returns whether any day failed.
+pub fn check_days(
+    days: &BTreeMap<usize, Box<dyn DayTrait>>,
+    inputs_dir: Option<&Path>,
+    record_answers: bool,
+) -> Result<bool> {
+    println!(
+        "{}",
+        format!(
+            "{:<5}{:<32}{:<12}{:<12}",
+            "Day", "Title", "Part 1", "Part 2"
+        )
+        .bold()
+    );
+    let mut details = Vec::new();
+    let mut any_failed = false;
+    for (day_num, day) in days {
+        let check = check_day(*day_num, day.as_ref(), inputs_dir);
+        let recorded = if record_answers {
+            record(&check)?
+        } else {
+            Vec::new()
+        };
+        any_failed |= check.failed();
+        let cells: Vec<ColoredString> = match &check {
+            DayCheck::MissingInput(e) => {
+                details.push(format!("Day {day_num}: skipped, {e}"));
+                vec![status_cell("SKIPPED"), status_cell("SKIPPED")]
+            },
+            DayCheck::Invalid(e) => {
+                details.push(format!("Day {day_num}: invalid input: {e}"));
+                vec![status_cell("INVALID"), status_cell("INVALID")]
+            },
+            DayCheck::Checked { parts, .. } => parts
+                .iter()
+                .map(|part| match part.status() {
+                    Status::Pass => status_cell("PASS"),
+                    Status::Fail => {
+                        details.push(format!(
+                            "Day {day_num} part {}: expected {:?}, got {:?}",
+                            part.part,
+                            part.expected.as_deref().unwrap_or_default(),
+                            part.actual.as_deref().unwrap_or_default()
+                        ));
+                        status_cell("FAIL")
+                    },
+                    Status::Unrecorded if recorded.contains(&part.part) => status_cell("RECORDED"),
+                    Status::Unrecorded => status_cell("UNRECORDED"),
+                    Status::Unsolved => {
+                        if let Err(e) = &part.actual {
+                            details.push(format!("Day {day_num} part {}: {e}", part.part));
+                        }
+                        status_cell("UNSOLVED")
+                    },
+                })
+                .collect(),
+        };
+        println!(
+            "{:<5}{:<32}{}{}",
+            day_num,
+            day.get_title(),
+            cells[0],
+            cells[1]
+        );
+    }
+    if !details.is_empty() {
+        println!();
+        for detail in details {
+            println!("{}", detail);
+        }
+    }
+    Ok(any_failed)
+}
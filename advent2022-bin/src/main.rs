@@ -1,8 +1,26 @@
-use advent2022_lib::{get_days, get_input};
+use std::io::Read;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use std::{fs, io, thread};
+
+use advent2022_lib::params::{parse_override, ParamSettings};
+use advent2022_lib::{
+    example_file_stem, find_input, get_days, get_input_from, DayTrait, Example, Part, BOTH_PARTS,
+};
+use color_eyre::eyre::{eyre, Result};
 use color_eyre::Report;
 use colored::*;
+use report::{Format, Outcome, Reporter};
 use structopt::StructOpt;
 
+mod check;
+mod new;
+mod pool;
+mod repl;
+mod report;
+mod watch;
+
 #[derive(StructOpt)]
 struct Cli {
     puzzle: Option<usize>,
@@ -13,70 +31,259 @@ struct Cli {
     #[structopt(long)]
     parallel: bool,
 
+    /// How many days to run at once with --parallel, by default one per CPU
+    #[structopt(long, requires = "parallel")]
+    jobs: Option<usize>,
+
+    /// Run on the day's first example, or on another one given by name or number, like --example 2
     #[structopt(long)]
-    example: bool,
+    example: Option<Option<String>>,
+
+    /// Only run one part of the puzzle
+    #[structopt(long, possible_values = &["1", "2"])]
+    part: Option<Part>,
+
+    /// Directory holding the dayNN.in.txt inputs, instead of $ADVENT2022_INPUTS or ./inputs
+    #[structopt(long, parse(from_os_str))]
+    inputs: Option<PathBuf>,
+
+    /// Read the input for a single day from a file, or from stdin with -
+    #[structopt(long, conflicts_with_all = &["all", "parallel", "example"])]
+    input: Option<String>,
+
+    /// Re-run the day whenever its input, example or --input file changes
+    #[structopt(long, conflicts_with_all = &["all", "parallel"])]
+    watch: bool,
+
+    /// Override a day's parameter, like --param row=10, on top of its input or example defaults
+    #[structopt(long = "param", number_of_values = 1, parse(try_from_str = parse_override))]
+    params: Vec<(String, String)>,
+
+    /// Also show how each answer was found, for the days that can explain themselves
+    #[structopt(long, conflicts_with_all = &["all", "parallel"])]
+    explain: bool,
+
+    /// Write the results as human-readable text, or as JSON or CSV for other tools
+    #[structopt(long, default_value = "text", possible_values = &["text", "json", "csv"])]
+    format: Format,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
 }
 
-fn print_day<O: std::fmt::Display>(
+#[derive(StructOpt)]
+enum Command {
+    /// Scaffold a new day from the template and register it
+    New { day: usize },
+    /// Compare every day's answers with the recorded inputs/dayNN.out.txt
+    Check {
+        /// Record the answers of solved parts that have no recorded answer yet
+        #[structopt(long)]
+        record: bool,
+    },
+    /// Explore days interactively, keeping the parsed input between commands
+    Repl,
+}
+
+/// The day's first example, or the one with the given name or number
+fn find_example(
+    day: &dyn DayTrait,
+    selector: Option<&str>,
+) -> Result<(usize, &'static Example), String> {
+    let Some(selector) = selector else {
+        return Ok((0, &day.get_examples()[0]));
+    };
+    day.find_example(selector).ok_or_else(|| {
+        let names: Vec<_> = day
+            .get_examples()
+            .iter()
+            .map(|example| example.name)
+            .collect();
+        format!(
+            "there is no example {selector:?}, try one of {} or a number up to {}",
+            names.join(", "),
+            names.len()
+        )
+    })
+}
+
+/// The selected example, or the day's input from the inputs directory, with the settings to
+/// run it with
+fn load_input(
+    day_num: usize,
+    day: &dyn DayTrait,
+    example: Option<Option<&str>>,
+    inputs: Option<&Path>,
+    overrides: &[(String, String)],
+) -> Result<(String, ParamSettings), String> {
+    match example {
+        Some(selector) => {
+            let (_, example) = find_example(day, selector)?;
+            Ok((example.input.to_owned(), example.settings(overrides)))
+        },
+        None => {
+            let input = get_input_from(day_num, inputs).map_err(|e| e.to_string())?;
+            Ok((input, input_settings(overrides)))
+        },
+    }
+}
+
+fn input_settings(overrides: &[(String, String)]) -> ParamSettings {
+    ParamSettings {
+        example: false,
+        overrides: overrides.to_vec(),
+    }
+}
+
+/// Run the given parts of a day on its example or input
+fn run_day(
     day_num: usize,
-    display: (&'static str, &'static str),
-    result: (O, O),
+    day: &dyn DayTrait,
+    example: Option<Option<&str>>,
+    inputs: Option<&Path>,
+    overrides: &[(String, String)],
+    parts: &[Part],
+) -> Outcome {
+    match load_input(day_num, day, example, inputs, overrides) {
+        Ok((input, settings)) => match day.run_with(&input, parts, &settings) {
+            Ok(run) => Outcome::Ran(run),
+            Err(e) => Outcome::InvalidInput(e),
+        },
+        Err(e) => Outcome::MissingInput(e),
+    }
+}
+
+/// Run and report a single day on an input, if it could be read, then explain its answers
+fn report_day(
+    day_num: usize,
+    day: &dyn DayTrait,
+    input: Result<(String, ParamSettings), String>,
+    parts: &[Part],
+    format: Format,
+    explain: bool,
+) -> Result<()> {
+    let outcome = match &input {
+        Ok((input, settings)) => match day.run_with(input, parts, settings) {
+            Ok(run) => Outcome::Ran(run),
+            Err(e) => Outcome::InvalidInput(e),
+        },
+        Err(e) => Outcome::MissingInput(e.clone()),
+    };
+    let mut reporter = Reporter::new(format);
+    reporter.day(day_num, day, outcome);
+    reporter.finish(false, None)?;
+    if let (true, Ok((input, settings))) = (explain, &input) {
+        explain_day(day_num, day, input, settings, parts);
+    }
+    Ok(())
+}
+
+/// Print how each part's answer was found, for the days that can explain themselves
+fn explain_day(
+    day_num: usize,
+    day: &dyn DayTrait,
+    input: &str,
+    settings: &ParamSettings,
+    parts: &[Part],
 ) {
-    println!("Day {}", day_num);
-    println!(
-        "Part 1: {}",
-        display.0.replace("{answer}", &result.0.to_string())
-    );
-    println!(
-        "Part 2: {}",
-        display.1.replace("{answer}", &result.1.to_string())
-    );
-    println!();
+    // the report already shows why the input did not parse, or a part is unsolved
+    let Ok(parsed) = day.parse_with(input, settings) else {
+        return;
+    };
+    for &part in parts {
+        match parsed.explain(part) {
+            Some(Ok(explanation)) => {
+                println!("{}", format!("Part {part} explained:").bold());
+                println!("{explanation}");
+                println!();
+            },
+            Some(Err(_)) => {},
+            None => {
+                println!("Day {day_num} cannot explain its answers");
+                return;
+            },
+        }
+    }
+}
+
+/// Read an input given with `--input`, where `-` means stdin
+fn read_input_arg(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
 }
 
 fn main() -> Result<(), Report> {
     setup()?;
 
-    println!("{}", "Advent Of Code 2022".bold().blue());
-    println!();
-
     let args = Cli::from_args();
+    if args.format == Format::Text {
+        println!("{}", "Advent Of Code 2022".bold().blue());
+        println!();
+    }
     let days = get_days();
+    match args.command {
+        Some(Command::New { day }) => {
+            let next_day = days.keys().next_back().map_or(1, |day_num| day_num + 1);
+            return new::new_day(day, next_day);
+        },
+        Some(Command::Check { record }) => {
+            if check::check_days(&days, args.inputs.as_deref(), record)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        },
+        Some(Command::Repl) => return repl::repl(&days, args.inputs.as_deref()),
+        None => {},
+    }
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => BOTH_PARTS.to_vec(),
+    };
+
+    if args.explain && args.format != Format::Text {
+        return Err(eyre!("--explain only works with --format text"));
+    }
+    // Watching clears the screen between runs, which would garble machine-readable output
+    if args.watch && args.format != Format::Text {
+        return Err(eyre!("--watch only works with --format text"));
+    }
+    let example = args.example.as_ref().map(Option::as_deref);
 
+    let mut reporter = Reporter::new(args.format);
     if args.all {
-        for (day_num, day) in days.into_iter() {
-            let input = if args.example {
-                day.get_example().to_owned()
-            } else {
-                get_input(day_num)
-            };
-            let (part1, part2) = day.both(&input).expect("invalid input");
-            print_day(day_num, day.get_display(), (part1, part2));
+        for (day_num, day) in days.iter() {
+            let outcome = run_day(
+                *day_num,
+                day.as_ref(),
+                example,
+                args.inputs.as_deref(),
+                &args.params,
+                &parts,
+            );
+            reporter.day(*day_num, day.as_ref(), outcome);
         }
+        reporter.finish(true, None)?;
     } else if args.parallel {
-        let threads = get_days().into_iter().map(|(day_num, day)| {
-            println!("Spawn day {}", day_num);
-            std::thread::spawn(move || {
-                let input = if args.example {
-                    day.get_example().to_owned()
-                } else {
-                    get_input(day_num)
-                };
-                (
-                    day_num,
-                    day.get_display(),
-                    day.both(&input).expect("invalid input"),
-                )
-            })
-        });
-        std::thread::yield_now();
-        std::thread::sleep(std::time::Duration::from_millis(50));
-        println!();
-        for thread in threads {
-            let (day_num, display, (part1, part2)) = thread.join().unwrap();
-            print_day(day_num, display, (part1, part2));
-        }
-    } else if !(args.all || args.parallel) {
+        let start = Instant::now();
+        let jobs = args
+            .jobs
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get));
+        pool::run_days(
+            days,
+            jobs,
+            |day_num, day| {
+                let inputs = args.inputs.as_deref();
+                run_day(day_num, day, example, inputs, &args.params, &parts)
+            },
+            |day_num, day, outcome| reporter.day(day_num, day, outcome),
+        );
+        reporter.finish(true, Some(start.elapsed()))?;
+    } else {
         let (day_num, day): (usize, _) = match args.puzzle {
             None => {
                 let (last_day_num, last_day) = days.iter().next_back().unwrap();
@@ -84,13 +291,53 @@ fn main() -> Result<(), Report> {
             },
             Some(day_num) => (day_num, days.get(&day_num).unwrap()),
         };
-        let input = if args.example {
-            day.get_example().to_owned()
-        } else {
-            get_input(day_num)
+        if args.watch {
+            let (path, settings) = match (&args.input, example) {
+                (Some(path), _) if path == "-" => return Err(eyre!("cannot watch stdin")),
+                (Some(path), _) => (PathBuf::from(path), input_settings(&args.params)),
+                (None, Some(selector)) => {
+                    let (index, example) =
+                        find_example(day.as_ref(), selector).map_err(|e| eyre!(e))?;
+                    let stem = example_file_stem(day_num, index, example);
+                    (watch::example_path(&stem)?, example.settings(&args.params))
+                },
+                (None, None) => (
+                    find_input(day_num, args.inputs.as_deref())?.0,
+                    input_settings(&args.params),
+                ),
+            };
+            return watch::watch(&path, |input| {
+                let input = input.map(|input| (input, settings.clone()));
+                report_day(
+                    day_num,
+                    day.as_ref(),
+                    input,
+                    &parts,
+                    args.format,
+                    args.explain,
+                )
+            });
+        }
+        let input = match &args.input {
+            Some(path) => read_input_arg(path)
+                .map(|input| (input, input_settings(&args.params)))
+                .map_err(|e| format!("{path}: {e}")),
+            None => load_input(
+                day_num,
+                day.as_ref(),
+                example,
+                args.inputs.as_deref(),
+                &args.params,
+            ),
         };
-        let (part1, part2) = day.both(&input).expect("invalid input");
-        print_day(day_num, day.get_display(), (part1, part2));
+        report_day(
+            day_num,
+            day.as_ref(),
+            input,
+            &parts,
+            args.format,
+            args.explain,
+        )?;
     }
 
     Ok(())
@@ -0,0 +1,248 @@
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use color_eyre::eyre::{eyre, Result};
+
+const TEMPLATE: &str = include_str!("../../advent2022-lib/src/day00_template.rs");
+const MAX_WIDTH: usize = 100;
+
+/// The workspace root, found by searching upwards from the working directory
+fn find_root() -> Result<PathBuf> {
+    let cwd = env::current_dir()?;
+    cwd.ancestors()
+        .find(|dir| dir.join("advent2022-lib/src/lib.rs").is_file())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| {
+            eyre!(
+                "could not find advent2022-lib/src/lib.rs in {} or any parent directory",
+                cwd.display()
+            )
+        })
+}
+
+/// Whether `module` is named like `day07`
+fn is_day_module(module: &str) -> bool {
+    module.len() == 5
+        && module.starts_with("day")
+        && module[3..].bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Add `module` to the `days!` invocation in `lib_rs`, wrapped the way rustfmt would
+fn register(lib_rs: &str, module: &str) -> Result<String> {
+    let start = lib_rs
+        .find("days! {\n")
+        .ok_or_else(|| eyre!("could not find the days! registration in lib.rs"))?
+        + "days! {\n".len();
+    let end = start
+        + lib_rs[start..]
+            .find("\n}")
+            .ok_or_else(|| eyre!("could not find the end of the days! registration in lib.rs"))?;
+    let mut modules: Vec<&str> = lib_rs[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect();
+    if let Some(unexpected) = modules.iter().find(|m| !is_day_module(m)) {
+        return Err(eyre!(
+            "expected only day modules in the days! registration in lib.rs, found {unexpected:?}"
+        ));
+    }
+    modules.push(module);
+    let mut lines = vec![String::from("   ")];
+    for module in modules {
+        let line = lines.last_mut().unwrap();
+        if line.len() + 1 + module.len() + 1 > MAX_WIDTH {
+            lines.push(format!("    {module},"));
+        } else {
+            line.push_str(&format!(" {module},"));
+        }
+    }
+    Ok(format!(
+        "{}{}{}",
+        &lib_rs[..start],
+        lines.join("\n"),
+        &lib_rs[end..]
+    ))
+}
+
+/// Add an empty malformed input corpus for `day_num` to the `MALFORMED` table in `test_rs`
+fn register_malformed(test_rs: &str, day_num: usize) -> Result<String> {
+    let prefix = "const MALFORMED: [(usize, &[&str]); ";
+    let len_start = test_rs
+        .find(prefix)
+        .ok_or_else(|| eyre!("could not find the MALFORMED table in test.rs"))?
+        + prefix.len();
+    let len_end = len_start
+        + test_rs[len_start..]
+            .find(']')
+            .ok_or_else(|| eyre!("could not find the length of the MALFORMED table in test.rs"))?;
+    let len: usize = test_rs[len_start..len_end].parse().map_err(|_| {
+        eyre!(
+            "expected the length of the MALFORMED table in test.rs, found {:?}",
+            &test_rs[len_start..len_end]
+        )
+    })?;
+    let end = len_end
+        + test_rs[len_end..]
+            .find("\n    ];")
+            .filter(|&end| !test_rs[len_end..len_end + end].contains("const "))
+            .ok_or_else(|| eyre!("could not find the end of the MALFORMED table in test.rs"))?;
+    Ok(format!(
+        "{}{}{}\n        ({day_num}, &[]),{}",
+        &test_rs[..len_start],
+        len + 1,
+        &test_rs[len_end..end],
+        &test_rs[end..]
+    ))
+}
+
+/// Add a fuzz target for `module` to the fuzz crate's manifest, unless it already has one
+fn register_fuzz_target(fuzz_toml: &str, module: &str) -> Result<String> {
+    if !fuzz_toml.contains("\n[[bin]]\n") {
+        return Err(eyre!("could not find the fuzz targets in fuzz/Cargo.toml"));
+    }
+    if fuzz_toml.contains(&format!("name = \"{module}\"")) {
+        return Ok(fuzz_toml.to_owned());
+    }
+    Ok(format!(
+        "{}\n\n[[bin]]\nname = \"{module}\"\npath = \"fuzz_targets/{module}.rs\"\ntest = false\ndoc = false\n",
+        fuzz_toml.trim_end()
+    ))
+}
+
+/// Create `path` with `contents` unless it already exists
+fn create_placeholder(path: &Path, contents: &str) -> Result<()> {
+    if path.exists() {
+        println!("Keeping existing {}", path.display());
+    } else {
+        fs::write(path, contents)?;
+        println!("Created {}", path.display());
+    }
+    Ok(())
+}
+
+/// Scaffold `dayNN` from the template and register it, refusing to touch an existing day
+pub fn new_day(day_num: usize, next_day_num: usize) -> Result<()> {
+    if day_num != next_day_num {
+        return Err(eyre!(
+            "days are numbered contiguously, so the next day to create is {next_day_num}, not {day_num}"
+        ));
+    }
+    let root = find_root()?;
+    let module = format!("day{:02}", day_num);
+    let source_path = root.join(format!("advent2022-lib/src/{module}.rs"));
+    if source_path.exists() {
+        return Err(eyre!("{} already exists", source_path.display()));
+    }
+    // Prepare every registration before writing anything, so that a failure leaves no half
+    // scaffolded day behind
+    let lib_path = root.join("advent2022-lib/src/lib.rs");
+    let lib_rs = register(&fs::read_to_string(&lib_path)?, &module)?;
+    let test_path = root.join("advent2022-lib/src/test.rs");
+    let test_rs = register_malformed(&fs::read_to_string(&test_path)?, day_num)?;
+    let fuzz_path = root.join("fuzz/Cargo.toml");
+    let fuzz_toml = register_fuzz_target(&fs::read_to_string(&fuzz_path)?, &module)?;
+
+    fs::write(&source_path, TEMPLATE.replace("day00", &module))?;
+    println!("Created {}", source_path.display());
+    create_placeholder(&root.join(format!("examples/{module}.in.txt")), "")?;
+    create_placeholder(&root.join(format!("examples/{module}.out.txt")), "")?;
+    fs::create_dir_all(root.join("inputs"))?;
+    create_placeholder(&root.join(format!("inputs/{module}.in.txt")), "")?;
+    create_placeholder(
+        &root.join(format!("fuzz/fuzz_targets/{module}.rs")),
+        &format!(
+            "#![no_main]\n\nuse libfuzzer_sys::fuzz_target;\n\nfuzz_target!(|data: &[u8]| advent2022_lib::fuzz::run({day_num}, data));\n"
+        ),
+    )?;
+    fs::write(&fuzz_path, fuzz_toml)?;
+    println!("Registered {module} in {}", fuzz_path.display());
+    fs::write(&test_path, test_rs)?;
+    println!(
+        "Registered {module}'s malformed inputs in {}",
+        test_path.display()
+    );
+    // Registered last, since the day only compiles once everything above is in place
+    fs::write(&lib_path, lib_rs)?;
+    println!("Registered {module} in {}", lib_path.display());
+    println!(
+        "Fill in the puzzle's example and its answers, and add malformed inputs to src/test.rs"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB_RS: &str = "mod check;\n\ndays! {\n    day01, day02,\n}\n\nfn main() {}\n";
+
+    const TEST_RS: &str = "    const MALFORMED: [(usize, &[&str]); 2] = [
+        (1, &[\"x\\n\"]),
+        (2, &[]),
+    ];
+
+    const UNSOLVABLE: [(usize, Part, &str); 1] = [
+        (1, Part::One, \"\\n\"),
+    ];
+";
+
+    const FUZZ_TOML: &str = "[package]\nname = \"advent2022-fuzz\"\n\n[[bin]]\nname = \"day01\"\npath = \"fuzz_targets/day01.rs\"\ntest = false\ndoc = false\n";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(LIB_RS, "day03").unwrap(),
+            "mod check;\n\ndays! {\n    day01, day02, day03,\n}\n\nfn main() {}\n"
+        );
+        let lib_rs = (3..=20).fold(String::from(LIB_RS), |lib_rs, day_num| {
+            register(&lib_rs, &format!("day{day_num:02}")).unwrap()
+        });
+        assert!(
+            lib_rs.contains("day13,\n    day14, day15, day16, day17, day18, day19, day20,\n}"),
+            "{lib_rs}"
+        );
+        assert!(lib_rs.lines().all(|line| line.len() <= MAX_WIDTH));
+    }
+
+    #[test]
+    fn test_register_missing_anchor() {
+        assert!(register("days!{\n    day01,\n}\n", "day02").is_err());
+        assert!(register("days! {\n    day01,", "day02").is_err());
+        assert!(register("days! {\n    day01, other,\n}\n", "day02").is_err());
+    }
+
+    #[test]
+    fn test_register_malformed() {
+        assert_eq!(
+            register_malformed(TEST_RS, 3).unwrap(),
+            TEST_RS
+                .replace("); 2]", "); 3]")
+                .replace("(2, &[]),\n", "(2, &[]),\n        (3, &[]),\n")
+        );
+    }
+
+    #[test]
+    fn test_register_malformed_missing_anchor() {
+        assert!(register_malformed(&TEST_RS.replace("MALFORMED", "MALFORMATIONS"), 3).is_err());
+        assert!(register_malformed(&TEST_RS.replace("); 2]", "); N]"), 3).is_err());
+        // Without its own end, the table must not be extended at the end of the next one
+        assert!(register_malformed(&TEST_RS.replacen("\n    ];", "\n];", 1), 3).is_err());
+    }
+
+    #[test]
+    fn test_register_fuzz_target() {
+        let fuzz_toml = register_fuzz_target(FUZZ_TOML, "day02").unwrap();
+        assert_eq!(
+            fuzz_toml,
+            format!(
+                "{FUZZ_TOML}\n[[bin]]\nname = \"day02\"\npath = \"fuzz_targets/day02.rs\"\ntest = false\ndoc = false\n"
+            )
+        );
+        assert_eq!(
+            register_fuzz_target(&fuzz_toml, "day02").unwrap(),
+            fuzz_toml
+        );
+        assert!(register_fuzz_target("[package]\nname = \"advent2022-fuzz\"\n", "day02").is_err());
+    }
+}
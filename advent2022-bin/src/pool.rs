@@ -0,0 +1,81 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use advent2022_lib::DayTrait;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+use crate::report::Outcome;
+
+/// The message a day panicked with, when it was given as a string
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic payload")
+    }
+}
+
+/// Run every day on a pool of `jobs` worker threads, with a progress bar per worker.
+///
+/// `report` is called on the calling thread as each day completes, so in completion order
+/// rather than day order. A day that panics is reported as `Outcome::Panicked` and the
+/// other days carry on.
+pub fn run_days(
+    days: BTreeMap<usize, Box<dyn DayTrait>>,
+    jobs: usize,
+    run: impl Fn(usize, &dyn DayTrait) -> Outcome + Sync,
+    mut report: impl FnMut(usize, &dyn DayTrait, Outcome),
+) {
+    let progress = MultiProgress::new();
+    let overall = progress.add(ProgressBar::new(days.len() as u64));
+    overall.set_style(
+        ProgressStyle::with_template("{bar:40} {pos}/{len} days ({elapsed})")
+            .expect("valid template"),
+    );
+    let queue = Mutex::new(days.into_iter());
+    let (sender, receiver) = mpsc::channel();
+
+    // The default hook would print every panic over the progress bars, and the message
+    // is reported with the day anyway
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| log::error!("{info}")));
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            let bar = progress.add(ProgressBar::new_spinner());
+            bar.set_style(ProgressStyle::with_template("{spinner} {msg}").expect("valid template"));
+            bar.enable_steady_tick(Duration::from_millis(100));
+            let (queue, run, sender) = (&queue, &run, sender.clone());
+            scope.spawn(move || {
+                loop {
+                    // Take the next day, releasing the lock before running it
+                    let next = queue.lock().unwrap().next();
+                    let Some((day_num, day)) = next else { break };
+                    bar.set_message(format!("Day {}: {}", day_num, day.get_title()));
+                    let outcome =
+                        panic::catch_unwind(AssertUnwindSafe(|| run(day_num, day.as_ref())))
+                            .unwrap_or_else(|payload| {
+                                Outcome::Panicked(panic_message(payload.as_ref()))
+                            });
+                    if sender.send((day_num, day, outcome)).is_err() {
+                        break;
+                    }
+                }
+                bar.finish_and_clear();
+            });
+        }
+        drop(sender);
+
+        for (day_num, day, outcome) in receiver {
+            overall.inc(1);
+            progress.suspend(|| report(day_num, day.as_ref(), outcome));
+        }
+    });
+    panic::set_hook(hook);
+    overall.finish_and_clear();
+}
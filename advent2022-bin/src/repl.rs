@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use advent2022_lib::params::{parse_override, ParamSettings};
+use advent2022_lib::{get_input_from, DayTrait, ParsedDay, Part, BOTH_PARTS};
+use color_eyre::eyre::{eyre, Result};
+use colored::*;
+
+use crate::report::part_display;
+
+const HELP: &str = "\
+day N             select a day, dropping any parsed input
+example [NAME|N]  parse the day's first example, or another one, with the parameters it was written for
+examples          list the day's examples
+input             parse the day's input from the inputs directory
+load FILE         parse the input in FILE
+paste [MARKER]    parse lines typed or pasted up to a line with just MARKER, by default EOF
+params            list the parsed day's parameters
+set NAME=VALUE    change a parameter, keeping the parsed input
+part 1|2          run a part on the parsed input
+time              run both parts on the parsed input, with the time it took to parse
+show parsed       print the parsed input's Debug form
+help              print this help
+quit              leave the REPL";
+
+/// The REPL's selected day and the input parsed for it, kept between commands
+struct Session<'a> {
+    days: &'a BTreeMap<usize, Box<dyn DayTrait>>,
+    inputs_dir: Option<&'a Path>,
+    day_num: usize,
+    parsed: Option<(Box<dyn ParsedDay>, Duration)>,
+}
+
+impl<'a> Session<'a> {
+    fn day(&self) -> &'a dyn DayTrait {
+        self.days[&self.day_num].as_ref()
+    }
+
+    fn parsed(&self) -> Result<&dyn ParsedDay> {
+        self.parsed
+            .as_ref()
+            .map(|(parsed, _)| parsed.as_ref())
+            .ok_or_else(|| eyre!("nothing parsed yet, try example, input, load or paste"))
+    }
+
+    fn select(&mut self, day_num: usize) -> Result<()> {
+        if !self.days.contains_key(&day_num) {
+            return Err(eyre!("there is no day {day_num}"));
+        }
+        self.day_num = day_num;
+        self.parsed = None;
+        println!("Day {}: {}", day_num, self.day().get_title());
+        Ok(())
+    }
+
+    fn parse(&mut self, input: &str, source: &str, settings: &ParamSettings) -> Result<()> {
+        let start = Instant::now();
+        let parsed = self.day().parse_with(input, settings)?;
+        let duration = start.elapsed();
+        println!(
+            "Parsed {} for day {} {}",
+            source,
+            self.day_num,
+            format!("({:?})", duration).dimmed()
+        );
+        self.parsed = Some((parsed, duration));
+        Ok(())
+    }
+
+    fn run_part(&self, part: Part) -> Result<()> {
+        let parsed = self.parsed()?;
+        let start = Instant::now();
+        let answer = parsed.part(part);
+        let duration = format!("({:?})", start.elapsed()).dimmed();
+        match answer {
+            Ok(answer) => println!(
+                "Part {}: {} {}",
+                part,
+                part_display(self.day().get_display(), part).replace("{answer}", &answer),
+                duration
+            ),
+            Err(e) => println!(
+                "Part {}: {} {} {}",
+                part,
+                "Unsolved:".red().bold(),
+                e,
+                duration
+            ),
+        }
+        Ok(())
+    }
+
+    fn params(&self) -> Result<()> {
+        let params = self.parsed()?.params();
+        if params.is_empty() {
+            println!("Day {} has no parameters", self.day_num);
+        }
+        for param in params {
+            println!(
+                "{}={} {}",
+                param.name,
+                param.value,
+                format!("({})", param.doc).dimmed()
+            );
+        }
+        Ok(())
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        let (parsed, _) = self
+            .parsed
+            .as_mut()
+            .ok_or_else(|| eyre!("nothing parsed yet, try example, input, load or paste"))?;
+        parsed.set_param(name, value)?;
+        Ok(())
+    }
+
+    fn time(&self) -> Result<()> {
+        if let Some((_, duration)) = &self.parsed {
+            println!("{}", format!("Parse: {:?}", duration).dimmed());
+        }
+        for part in BOTH_PARTS {
+            self.run_part(part)?;
+        }
+        Ok(())
+    }
+
+    /// Run one line's command, returning whether to carry on
+    fn command(
+        &mut self,
+        line: &str,
+        lines: &mut impl Iterator<Item = io::Result<String>>,
+    ) -> Result<bool> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {},
+            ["day", day_num] => self.select(day_num.parse()?)?,
+            ["example"] | ["example", _] => {
+                let examples = self.day().get_examples();
+                let example = match words.get(1) {
+                    None => &examples[0],
+                    Some(selector) => {
+                        let (_, example) = self.day().find_example(selector).ok_or_else(|| {
+                            eyre!("there is no example {selector:?}, try examples")
+                        })?;
+                        example
+                    },
+                };
+                let source = format!("the {} example", example.name);
+                self.parse(example.input, &source, &example.settings(&[]))?
+            },
+            ["examples"] => {
+                for (index, example) in self.day().get_examples().iter().enumerate() {
+                    println!("{} {}", index + 1, example.name);
+                }
+            },
+            ["input"] => {
+                let input = get_input_from(self.day_num, self.inputs_dir)?;
+                self.parse(&input, "the input", &ParamSettings::default())?
+            },
+            ["load", path] => {
+                let input = fs::read_to_string(path).map_err(|e| eyre!("{path}: {e}"))?;
+                self.parse(&input, path, &ParamSettings::default())?
+            },
+            ["paste"] | ["paste", _] => {
+                let marker = words.get(1).copied().unwrap_or("EOF");
+                println!(
+                    "{}",
+                    format!("Paste the input, then {marker} on its own line").dimmed()
+                );
+                let mut input = String::new();
+                for line in lines.by_ref() {
+                    let line = line?;
+                    if line.trim_end() == marker {
+                        break;
+                    }
+                    input.push_str(&line);
+                    input.push('\n');
+                }
+                self.parse(&input, "the pasted input", &ParamSettings::default())?
+            },
+            ["part", part] => {
+                self.run_part(part.parse().map_err(|_| eyre!("parts are 1 or 2"))?)?
+            },
+            ["params"] => self.params()?,
+            ["set", name, value] => self.set_param(name, value)?,
+            ["set", assignment] => {
+                let (name, value) = parse_override(assignment)?;
+                self.set_param(&name, &value)?
+            },
+            ["time"] => self.time()?,
+            ["show", "parsed"] => println!("{:#?}", self.parsed()?),
+            ["help"] => println!("{}", HELP),
+            ["quit"] | ["exit"] => return Ok(false),
+            _ => return Err(eyre!("unknown command {line:?}, try help")),
+        }
+        Ok(true)
+    }
+}
+
+/// Read commands from stdin until `quit` or the end of input, starting on the latest day
+pub fn repl(days: &BTreeMap<usize, Box<dyn DayTrait>>, inputs_dir: Option<&Path>) -> Result<()> {
+    let mut session = Session {
+        days,
+        inputs_dir,
+        day_num: *days
+            .keys()
+            .next_back()
+            .ok_or_else(|| eyre!("no days registered"))?,
+        parsed: None,
+    };
+    println!("{}", "Type help for the commands".dimmed());
+    println!("Day {}: {}", session.day_num, session.day().get_title());
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("{} ", format!("day {}>", session.day_num).bold());
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else { break };
+        match session.command(line?.trim(), &mut lines) {
+            Ok(true) => {},
+            Ok(false) => break,
+            Err(e) => println!("{} {}", "Error:".red().bold(), e),
+        }
+    }
+    println!();
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use std::fmt::Display;
+use std::io;
+use std::time::Duration;
+
+use advent2022_lib::{DayRun, DayTrait, ParseError, Part, PartRun};
+use color_eyre::eyre::Result;
+use colored::*;
+use serde::Serialize;
+use strum_macros::EnumString;
+
+/// How the results of a run are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// What happened when running one day
+pub enum Outcome {
+    Ran(DayRun),
+    MissingInput(String),
+    InvalidInput(ParseError),
+    Panicked(String),
+}
+
+impl Outcome {
+    fn status(&self) -> &'static str {
+        match self {
+            Self::Ran(run) if run.parts.iter().all(|part| part.answer.is_ok()) => "OK",
+            Self::Ran(_) => "UNSOLVED",
+            Self::MissingInput(_) => "NO INPUT",
+            Self::InvalidInput(_) => "INVALID",
+            Self::Panicked(_) => "PANICKED",
+        }
+    }
+}
+
+/// A line of the summary printed once every day of a parallel run has completed
+struct SummaryRow {
+    day: usize,
+    title: &'static str,
+    status: &'static str,
+    duration: Option<Duration>,
+}
+
+#[derive(Serialize)]
+struct PartReport {
+    part: u8,
+    answer: Option<String>,
+    sentence: Option<String>,
+    duration_secs: f64,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DayReport {
+    day: usize,
+    title: &'static str,
+    parse_duration_secs: Option<f64>,
+    total_duration_secs: Option<f64>,
+    parts: Vec<PartReport>,
+    error: Option<String>,
+}
+
+/// A CSV row: one per part, or a single row without part columns when the day did not run
+#[derive(Clone, Copy, Serialize)]
+struct CsvRow<'a> {
+    day: usize,
+    title: &'static str,
+    parse_duration_secs: Option<f64>,
+    part: Option<u8>,
+    answer: Option<&'a str>,
+    sentence: Option<&'a str>,
+    duration_secs: Option<f64>,
+    error: Option<&'a str>,
+}
+
+fn part_num(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+pub fn part_display(display: (&'static str, &'static str), part: Part) -> &'static str {
+    match part {
+        Part::One => display.0,
+        Part::Two => display.1,
+    }
+}
+
+impl DayReport {
+    fn new(day_num: usize, day: &dyn DayTrait, outcome: &Outcome) -> Self {
+        let mut report = Self {
+            day: day_num,
+            title: day.get_title(),
+            parse_duration_secs: None,
+            total_duration_secs: None,
+            parts: Vec::new(),
+            error: None,
+        };
+        match outcome {
+            Outcome::Ran(run) => {
+                report.parse_duration_secs = Some(run.parse_duration.as_secs_f64());
+                report.total_duration_secs = Some(run.total_duration().as_secs_f64());
+                report.parts = run
+                    .parts
+                    .iter()
+                    .map(|part_run| PartReport {
+                        part: part_num(part_run.part),
+                        answer: part_run.answer.as_ref().ok().cloned(),
+                        sentence: part_run.answer.as_ref().ok().map(|answer| {
+                            part_display(day.get_display(), part_run.part)
+                                .replace("{answer}", answer)
+                        }),
+                        duration_secs: part_run.duration.as_secs_f64(),
+                        error: part_run.answer.as_ref().err().map(ToString::to_string),
+                    })
+                    .collect();
+            },
+            Outcome::MissingInput(e) => report.error = Some(format!("Missing input: {e}")),
+            Outcome::InvalidInput(e) => report.error = Some(format!("Invalid input: {e}")),
+            Outcome::Panicked(e) => report.error = Some(format!("Panicked: {e}")),
+        }
+        report
+    }
+
+    fn csv_rows(&self) -> Vec<CsvRow<'_>> {
+        let day_row = CsvRow {
+            day: self.day,
+            title: self.title,
+            parse_duration_secs: self.parse_duration_secs,
+            part: None,
+            answer: None,
+            sentence: None,
+            duration_secs: None,
+            error: self.error.as_deref(),
+        };
+        if self.parts.is_empty() {
+            return vec![day_row];
+        }
+        self.parts
+            .iter()
+            .map(|part| CsvRow {
+                part: Some(part.part),
+                answer: part.answer.as_deref(),
+                sentence: part.sentence.as_deref(),
+                duration_secs: Some(part.duration_secs),
+                error: part.error.as_deref(),
+                ..day_row
+            })
+            .collect()
+    }
+}
+
+fn print_part(display: &str, run: &PartRun) {
+    match &run.answer {
+        Ok(answer) => println!(
+            "Part {}: {} {}",
+            run.part,
+            display.replace("{answer}", answer),
+            format!("({:?})", run.duration).dimmed()
+        ),
+        Err(e) => println!(
+            "Part {}: {} {} {}",
+            run.part,
+            "Unsolved:".red().bold(),
+            e,
+            format!("({:?})", run.duration).dimmed()
+        ),
+    }
+}
+
+fn print_day(day_num: usize, display: (&'static str, &'static str), run: &DayRun) {
+    println!("Day {}", day_num);
+    println!("{}", format!("Parse: {:?}", run.parse_duration).dimmed());
+    for part_run in &run.parts {
+        print_part(part_display(display, part_run.part), part_run);
+    }
+    println!("{}", format!("Total: {:?}", run.total_duration()).dimmed());
+    println!();
+}
+
+fn print_error(day_num: usize, heading: &str, error: impl Display) {
+    println!("Day {}", day_num);
+    println!("{}", heading.red().bold());
+    println!("{}", error);
+    println!();
+}
+
+/// Writes each day's outcome in the chosen format.
+///
+/// Text is printed as each day comes in, while JSON and CSV are collected and written
+/// by `finish`, so that stdout holds a single document.
+pub struct Reporter {
+    format: Format,
+    total: Duration,
+    reports: Vec<DayReport>,
+    summary: Vec<SummaryRow>,
+}
+
+impl Reporter {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            total: Duration::ZERO,
+            reports: Vec::new(),
+            summary: Vec::new(),
+        }
+    }
+
+    pub fn day(&mut self, day_num: usize, day: &dyn DayTrait, outcome: Outcome) {
+        if self.format != Format::Text {
+            self.reports.push(DayReport::new(day_num, day, &outcome));
+            return;
+        }
+        self.summary.push(SummaryRow {
+            day: day_num,
+            title: day.get_title(),
+            status: outcome.status(),
+            duration: match &outcome {
+                Outcome::Ran(run) => Some(run.total_duration()),
+                _ => None,
+            },
+        });
+        match outcome {
+            Outcome::Ran(run) => {
+                self.total += run.total_duration();
+                print_day(day_num, day.get_display(), &run)
+            },
+            Outcome::MissingInput(e) => print_error(day_num, "Missing input:", e),
+            Outcome::InvalidInput(e) => print_error(day_num, "Invalid input:", e),
+            Outcome::Panicked(e) => print_error(day_num, "Panicked:", e),
+        }
+    }
+
+    fn print_summary(&mut self) {
+        self.summary.sort_by_key(|row| row.day);
+        println!("{}", "Summary".bold());
+        println!(
+            "{}",
+            format!("{:<5}{:<32}{:<10}{}", "Day", "Title", "Status", "Time").bold()
+        );
+        for row in &self.summary {
+            let status = format!("{:<10}", row.status);
+            let status = match row.status {
+                "OK" => status.green(),
+                "UNSOLVED" => status.yellow(),
+                _ => status.red(),
+            };
+            let duration = row
+                .duration
+                .map(|duration| format!("{:?}", duration))
+                .unwrap_or_default();
+            println!("{:<5}{:<32}{}{}", row.day, row.title, status, duration);
+        }
+        println!();
+    }
+
+    /// Write the collected reports in day order, or with text the total time when running
+    /// several days. A parallel run passes its wall-clock time, and as its days were printed
+    /// in completion order, the text also ends with a summary in day order.
+    pub fn finish(mut self, several_days: bool, wall_clock: Option<Duration>) -> Result<()> {
+        self.reports.sort_by_key(|report| report.day);
+        match self.format {
+            Format::Text => {
+                if wall_clock.is_some() {
+                    self.print_summary();
+                }
+                if several_days {
+                    println!("{} {:?}", "Total time:".bold(), self.total);
+                }
+                if let Some(wall_clock) = wall_clock {
+                    println!("{} {:?}", "Wall-clock time:".bold(), wall_clock);
+                }
+            },
+            Format::Json => {
+                serde_json::to_writer_pretty(io::stdout(), &self.reports)?;
+                println!();
+            },
+            Format::Csv => {
+                let mut writer = csv::Writer::from_writer(io::stdout());
+                for row in self.reports.iter().flat_map(DayReport::csv_rows) {
+                    writer.serialize(row)?;
+                }
+                writer.flush()?;
+            },
+        }
+        Ok(())
+    }
+}
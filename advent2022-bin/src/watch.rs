@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use color_eyre::eyre::{eyre, Result};
+use colored::*;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The example on disk, rather than the copy compiled into the binary, so edits are seen
+pub fn example_path(stem: &str) -> Result<PathBuf> {
+    let file_name = format!("{stem}.in.txt");
+    ["examples", "../examples"]
+        .into_iter()
+        .map(|dir| Path::new(dir).join(&file_name))
+        .find(|path| path.is_file())
+        .ok_or_else(|| eyre!("could not find {file_name} in examples or ../examples"))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Call `run` with the contents of `path` now and whenever it changes, until interrupted.
+///
+/// The screen is cleared before each run. The file is polled rather than watched with OS
+/// notifications, which also copes with editors that replace the file when saving. Changes
+/// to a day's source need a rebuild, so run under `cargo watch` to pick those up too.
+pub fn watch(path: &Path, mut run: impl FnMut(Result<String, String>) -> Result<()>) -> Result<()> {
+    let mut last_modified = None;
+    loop {
+        let modified = modified(path);
+        if last_modified != Some(modified) {
+            last_modified = Some(modified);
+            print!("\x1B[2J\x1B[H");
+            println!(
+                "{}",
+                format!("Watching {}, press Ctrl-C to stop", path.display()).dimmed()
+            );
+            println!();
+            run(fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display())))?;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,54 @@
+use advent2022_lib::params::ParamSettings;
+use advent2022_lib::{get_days, get_input, Part, BOTH_PARTS};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode};
+
+/// Benchmark IDs are `dayNN/{parse,part1,part2}/{example,input}`, so they stay stable across runs
+pub fn benchmark(c: &mut Criterion) {
+    for (day_num, day) in get_days() {
+        let mut group = c.benchmark_group(format!("day{:02}", day_num));
+        // some days take seconds per iteration, so keep the sample count low
+        group.sample_size(10);
+        group.sampling_mode(SamplingMode::Flat);
+        let input = get_input(day_num);
+        let inputs = [
+            (
+                "example",
+                Some(day.get_example().to_owned()),
+                ParamSettings::example(),
+            ),
+            ("input", input.ok(), ParamSettings::default()),
+        ];
+        for (input_name, input, settings) in inputs {
+            let input = match input {
+                Some(input) => input,
+                None => {
+                    eprintln!("day {day_num} has no {input_name}, skipping");
+                    continue;
+                },
+            };
+            let parsed = match day.parse_with(&input, &settings) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("day {day_num} {input_name} does not parse, skipping: {e}");
+                    continue;
+                },
+            };
+            group.bench_with_input(BenchmarkId::new("parse", input_name), &input, |b, input| {
+                b.iter(|| day.parse_with(input, &settings))
+            });
+            for part in BOTH_PARTS {
+                let name = match part {
+                    Part::One => "part1",
+                    Part::Two => "part2",
+                };
+                group.bench_function(BenchmarkId::new(name, input_name), |b| {
+                    b.iter(|| parsed.part(part))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,306 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::{find_input, DayTrait, InputError, ParseError, Part, SolveResult, BOTH_PARTS};
+
+/// The recorded answers live next to the input, e.g. `inputs/day07.out.txt`
+pub fn answers_path(input_path: &Path) -> PathBuf {
+    let file_name = input_path
+        .file_name()
+        .map(|name| name.to_string_lossy().replace(".in.txt", ".out.txt"))
+        .unwrap_or_default();
+    input_path.with_file_name(file_name)
+}
+
+/// The part a `part N:` section heading starts, and the rest of its line
+fn section_heading(line: &str) -> Option<(usize, &str)> {
+    let (part, rest) = line.strip_prefix("part ")?.split_once(':')?;
+    let index = match part {
+        "1" => 0,
+        "2" => 1,
+        _ => return None,
+    };
+    Some((index, rest.strip_prefix(' ').unwrap_or(rest)))
+}
+
+/// Read recorded answers, in either of two formats.
+///
+/// Sections like `part 1: 24000` hold one part each, and a part without a section has no
+/// recorded answer. An answer spanning several lines, or starting or ending with whitespace
+/// like day 10's full-width spaces, instead follows an empty `part N:` heading with each
+/// of its lines between `|`s, so that editors stripping trailing whitespace cannot change it.
+///
+/// Files without sections hold part 1 on the first line, and part 2 on the remaining lines.
+pub fn parse_answers(recorded: &str) -> (Option<String>, Option<String>) {
+    if section_heading(recorded.lines().next().unwrap_or_default()).is_none() {
+        let mut lines = recorded.trim_end().lines();
+        let part1 = lines.next().filter(|line| !line.is_empty());
+        let part2 = lines.collect::<Vec<_>>().join("\n");
+        return (
+            part1.map(String::from),
+            (!part2.is_empty()).then_some(part2),
+        );
+    }
+    let mut answers: [Option<String>; 2] = Default::default();
+    let mut section = 0;
+    for line in recorded.lines() {
+        if let Some((index, rest)) = section_heading(line) {
+            section = index;
+            answers[index] = (!rest.is_empty()).then(|| rest.to_owned());
+            continue;
+        }
+        let Some(line) = line.strip_prefix('|').and_then(|line| line.strip_suffix('|')) else {
+            continue;
+        };
+        match &mut answers[section] {
+            Some(answer) => {
+                answer.push('\n');
+                answer.push_str(line);
+            },
+            answer @ None => *answer = Some(line.to_owned()),
+        }
+    }
+    let [part1, part2] = answers;
+    (part1, part2)
+}
+
+fn format_answer(part: u8, answer: &str) -> String {
+    if answer.contains('\n') || answer.trim() != answer || answer.is_empty() {
+        let lines: String = answer
+            .split('\n')
+            .map(|line| format!("|{line}|\n"))
+            .collect();
+        format!("part {part}:\n{lines}")
+    } else {
+        format!("part {part}: {answer}\n")
+    }
+}
+
+pub fn format_answers(part1: &str, part2: &str) -> String {
+    format_answer(1, part1) + &format_answer(2, part2)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// Solved, but there is no recorded answer to compare with
+    Unrecorded,
+    Unsolved,
+}
+
+#[derive(Debug)]
+pub struct PartCheck {
+    pub part: Part,
+    pub expected: Option<String>,
+    pub actual: SolveResult<String>,
+}
+
+impl PartCheck {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.actual) {
+            (_, Err(_)) => Status::Unsolved,
+            (None, Ok(_)) => Status::Unrecorded,
+            (Some(expected), Ok(actual)) if expected == actual => Status::Pass,
+            (Some(_), Ok(_)) => Status::Fail,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DayCheck {
+    MissingInput(InputError),
+    Invalid(ParseError),
+    Checked {
+        answers_path: PathBuf,
+        parts: Vec<PartCheck>,
+    },
+}
+
+impl DayCheck {
+    /// Whether any answer is wrong or missing; answers with nothing recorded do not fail, and
+    /// neither do days without an input, which are skipped
+    pub fn failed(&self) -> bool {
+        match self {
+            Self::Checked { parts, .. } => parts
+                .iter()
+                .any(|part| matches!(part.status(), Status::Fail | Status::Unsolved)),
+            Self::Invalid(_) => true,
+            Self::MissingInput(_) => false,
+        }
+    }
+}
+
+/// Solve a day's real input and compare the answers with the recorded ones
+pub fn check_day(day_num: usize, day: &dyn DayTrait, inputs_dir: Option<&Path>) -> DayCheck {
+    let (input_path, input) = match find_input(day_num, inputs_dir) {
+        // A newly scaffolded day has an empty placeholder until its input is saved
+        Ok((input_path, input)) if input.trim().is_empty() => {
+            return DayCheck::MissingInput(InputError {
+                day: day_num,
+                tried: vec![(
+                    input_path,
+                    io::Error::new(io::ErrorKind::InvalidData, "the file is empty"),
+                )],
+            })
+        },
+        Ok(found) => found,
+        Err(e) => return DayCheck::MissingInput(e),
+    };
+    let parsed = match day.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => return DayCheck::Invalid(e),
+    };
+    let answers_path = answers_path(&input_path);
+    let (expected1, expected2) = fs::read_to_string(&answers_path)
+        .map(|recorded| parse_answers(&recorded))
+        .unwrap_or_default();
+    let parts = BOTH_PARTS
+        .into_iter()
+        .zip([expected1, expected2])
+        .map(|(part, expected)| PartCheck {
+            part,
+            expected,
+            actual: parsed.part(part),
+        })
+        .collect();
+    DayCheck::Checked {
+        answers_path,
+        parts,
+    }
+}
+
+/// Record the answers of the solved parts that have no recorded answer yet, returning them.
+///
+/// Recorded answers are kept even when they no longer match, so that a solver that breaks
+/// cannot silently replace the answers it should be checked against.
+pub fn record(check: &DayCheck) -> io::Result<Vec<Part>> {
+    let DayCheck::Checked {
+        answers_path,
+        parts,
+    } = check
+    else {
+        return Ok(Vec::new());
+    };
+    let recording: Vec<Part> = parts
+        .iter()
+        .filter(|part| part.status() == Status::Unrecorded)
+        .map(|part| part.part)
+        .collect();
+    if recording.is_empty() {
+        return Ok(recording);
+    }
+    let answers: String = parts
+        .iter()
+        .zip(1..)
+        .filter_map(|(part, number)| {
+            let answer = match part.status() {
+                Status::Unrecorded => part.actual.as_deref().ok(),
+                _ => part.expected.as_deref(),
+            }?;
+            Some(format_answer(number, answer))
+        })
+        .collect();
+    fs::write(answers_path, answers)?;
+    Ok(recording)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn test_answers_path() {
+        assert_eq!(
+            answers_path(Path::new("../inputs/day07.in.txt")),
+            PathBuf::from("../inputs/day07.out.txt")
+        );
+    }
+
+    #[test]
+    fn test_answers_roundtrip() {
+        let image = "⬛⬛　　\n⬛⬛⬛　";
+        let recorded = format_answers("13140", image);
+        assert_eq!(recorded, "part 1: 13140\npart 2:\n|⬛⬛　　|\n|⬛⬛⬛　|\n");
+        assert_eq!(
+            parse_answers(&recorded),
+            (Some(String::from("13140")), Some(String::from(image)))
+        );
+        assert_eq!(
+            parse_answers(&format_answers(" padded", "CMZ")),
+            (Some(String::from(" padded")), Some(String::from("CMZ")))
+        );
+    }
+
+    #[test]
+    fn test_record_keeps_recorded_answers() {
+        // Unique to this test and process, so that concurrent test runs cannot share it
+        let answers_path = std::env::temp_dir().join(format!(
+            "advent2022-{}-test-record-keeps-recorded-answers.out.txt",
+            std::process::id()
+        ));
+        let check = DayCheck::Checked {
+            answers_path: answers_path.clone(),
+            parts: vec![
+                PartCheck {
+                    part: Part::One,
+                    expected: Some(String::from("24000")),
+                    actual: Ok(String::from("24001")),
+                },
+                PartCheck {
+                    part: Part::Two,
+                    expected: None,
+                    actual: Ok(String::from("45000")),
+                },
+            ],
+        };
+        assert_eq!(record(&check).unwrap(), vec![Part::Two]);
+        assert_eq!(
+            fs::read_to_string(&answers_path).unwrap(),
+            "part 1: 24000\npart 2: 45000\n"
+        );
+        assert!(check.failed(), "a wrong answer still fails once recorded");
+        fs::remove_file(answers_path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_input_skipped() {
+        let days = crate::get_days();
+        let check = check_day(1, days[&1].as_ref(), Some(Path::new("no-such-inputs")));
+        assert!(matches!(check, DayCheck::MissingInput(_)));
+        assert!(!check.failed(), "a day without an input is skipped");
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(
+            parse_answers("part 2: 36\n"),
+            (None, Some(String::from("36")))
+        );
+        assert_eq!(
+            parse_answers("part 1:\n|##..|\n\n|#..#|\npart 2: 45000\n"),
+            (
+                Some(String::from("##..\n#..#")),
+                Some(String::from("45000"))
+            )
+        );
+        assert_eq!(
+            parse_answers("24000\n45000\n"),
+            (Some(String::from("24000")), Some(String::from("45000")))
+        );
+        assert_eq!(
+            parse_answers("13140\n##..\n#..#\n"),
+            (
+                Some(String::from("13140")),
+                Some(String::from("##..\n#..#"))
+            )
+        );
+        assert_eq!(
+            parse_answers("24000\n"),
+            (Some(String::from("24000")), None)
+        );
+        assert_eq!(parse_answers(""), (None, None));
+    }
+}
@@ -1,21 +1,46 @@
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use std::str::FromStr;
+
+use crate::params::NO_PARAMS;
+use crate::parser::parse_lines;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveResult};
+
+#[derive(Debug)]
+pub struct Something(usize);
+
+impl FromStr for Something {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
 
 #[derive(Debug)]
-pub struct Something {}
+pub struct Somethings(Vec<Something>);
 
-pub fn parse(input: &str) -> ParseResult<Something> {
-    Ok(Something {})
+impl FromStr for Somethings {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse_lines(s)?))
+    }
+}
+
+pub fn parse(input: &str) -> ParseResult<Somethings> {
+    input.parse()
 }
 
-pub fn part1(something: &Something) -> PartOutput<usize> {
-    PartOutput { answer: 0 }
+pub fn part1(somethings: &Somethings, _params: &()) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
+        answer: somethings.0.iter().map(|something| something.0).sum(),
+    })
 }
 
-pub fn part2(_something: &Something) -> PartOutput<usize> {
-    PartOutput { answer: 0 }
+pub fn part2(_somethings: &Somethings, _params: &()) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput { answer: 0 })
 }
 
-pub const DAY: Day<Something, usize> = Day {
+pub const DAY: Day<Somethings, usize> = Day {
     title: "TITLE",
     display: (
         "Foobar foobar foobar {answer}",
@@ -25,8 +50,13 @@ pub const DAY: Day<Something, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day00.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day00.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
 
 #[cfg(test)]
@@ -34,26 +64,16 @@ mod tests {
     use test_log::test;
 
     use super::*;
-    use crate::get_input;
 
     #[test]
     fn test_example_part1() {
-        let something = parse(DAY.example).unwrap();
-        let result = play(&something);
-        assert_eq!(result, -1);
+        let somethings = parse(DAY.examples[0].input).unwrap();
+        assert!(part1(&somethings, &()).is_ok());
     }
 
     #[test]
     fn test_example_part2() {
-        let something = parse(DAY.example).unwrap();
-        let result = play(&something);
-        assert_eq!(result, -1);
-    }
-
-    #[test]
-    fn test_main() {
-        let something = parse(&get_input(0)).unwrap();
-        assert_eq!(part1(&something).answer.to_string(), "-1");
-        assert_eq!(part2(&something).answer.to_string(), "-1");
+        let somethings = parse(DAY.examples[0].input).unwrap();
+        assert!(part2(&somethings, &()).is_ok());
     }
 }
@@ -1,35 +1,38 @@
-use crate::{Day, DayCalc, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::parser::parse_lines;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
 
+#[derive(Debug)]
 pub struct Calories(Vec<Vec<usize>>);
 
 pub fn parse(input: &str) -> ParseResult<Calories> {
     let calories = input
         .split("\n\n")
-        .map(|block| block.lines().map(|line| line.parse::<usize>()).collect())
+        .map(|block| parse_lines(block).map_err(|e: ParseError| e.locate(input, block)))
         .collect::<Result<_, _>>()?;
     Ok(Calories(calories))
 }
 
-pub fn part1(calories: &Calories) -> PartOutput<usize> {
+pub fn part1(calories: &Calories, _params: &()) -> SolveResult<PartOutput<usize>> {
     let max_calories = calories
         .0
         .iter()
         .map(|items| items.iter().sum())
         .max()
-        .unwrap();
-    PartOutput {
+        .ok_or_else(|| SolveError::NoSolution(String::from("no elves")))?;
+    Ok(PartOutput {
         answer: max_calories,
-    }
+    })
 }
 
-pub fn part2(calories: &Calories) -> PartOutput<usize> {
+pub fn part2(calories: &Calories, _params: &()) -> SolveResult<PartOutput<usize>> {
     let mut sum_calories: Vec<usize> = calories.0.iter().map(|items| items.iter().sum()).collect();
     sum_calories.sort_unstable();
     sum_calories.reverse();
     let max_3_calories = sum_calories.iter().take(3).sum();
-    PartOutput {
+    Ok(PartOutput {
         answer: max_3_calories,
-    }
+    })
 }
 
 pub const DAY: Day<Calories, usize> = Day {
@@ -42,13 +45,56 @@ pub const DAY: Day<Calories, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day01.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day01.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
 
 #[cfg(test)]
 mod tests {
-    // use test_log::test;
-    // use super::*;
-    // use crate::get_input;
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use super::*;
+
+    /// The largest total of any `count` elves, trying every combination
+    fn brute_force_max(totals: &[usize], count: usize) -> usize {
+        if totals.len() <= count {
+            return totals.iter().sum();
+        }
+        let mut max = 0;
+        for (i, first) in totals.iter().enumerate() {
+            if count == 1 {
+                max = max.max(*first);
+                continue;
+            }
+            for (j, second) in totals.iter().enumerate().skip(i + 1) {
+                for third in totals.iter().skip(j + 1) {
+                    max = max.max(first + second + third);
+                }
+            }
+        }
+        max
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force(
+            elves in prop::collection::vec(prop::collection::vec(0..100usize, 1..5), 1..12)
+        ) {
+            let input = elves
+                .iter()
+                .map(|items| items.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let calories = parse(&input).unwrap();
+            let totals: Vec<usize> = elves.iter().map(|items| items.iter().sum()).collect();
+            prop_assert_eq!(part1(&calories, &()).unwrap().answer, brute_force_max(&totals, 1));
+            prop_assert_eq!(part2(&calories, &()).unwrap().answer, brute_force_max(&totals, 3));
+        }
+    }
 }
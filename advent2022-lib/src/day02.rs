@@ -2,7 +2,8 @@ use std::str::FromStr;
 
 use strum_macros::EnumString;
 
-use crate::{Day, DayCalc, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveResult};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Outcome {
@@ -119,16 +120,26 @@ pub fn parse(input: &str) -> ParseResult<StrategyGuide> {
         input
             .lines()
             .map(|line| {
-                let mut parts = line.split_whitespace();
-                let opponent = Opponent::from_str(parts.next().unwrap())?;
-                let response = SecondUnknown::from_str(parts.next().unwrap())?;
+                let (opponent, response) = match line.split_whitespace().collect::<Vec<_>>()[..] {
+                    [opponent, response] => (opponent, response),
+                    _ => {
+                        return Err(ParseError::Str(format!(
+                            "expected two columns, found {line:?}"
+                        ))
+                        .locate(input, line))
+                    },
+                };
+                let opponent = Opponent::from_str(opponent)
+                    .map_err(|e| ParseError::from(e).locate(input, opponent))?;
+                let response = SecondUnknown::from_str(response)
+                    .map_err(|e| ParseError::from(e).locate(input, response))?;
                 Ok((opponent, response))
             })
             .collect::<ParseResult<_>>()?,
     ))
 }
 
-pub fn part1(guide: &StrategyGuide) -> PartOutput<usize> {
+pub fn part1(guide: &StrategyGuide, _params: &()) -> SolveResult<PartOutput<usize>> {
     let scores: Vec<usize> = guide
         .0
         .iter()
@@ -137,12 +148,12 @@ pub fn part1(guide: &StrategyGuide) -> PartOutput<usize> {
             response.shape().score_shape() + response.shape().outcome(&opponent.shape()).score()
         })
         .collect();
-    PartOutput {
+    Ok(PartOutput {
         answer: scores.iter().sum(),
-    }
+    })
 }
 
-pub fn part2(guide: &StrategyGuide) -> PartOutput<usize> {
+pub fn part2(guide: &StrategyGuide, _params: &()) -> SolveResult<PartOutput<usize>> {
     let scores: Vec<usize> = guide
         .0
         .iter()
@@ -153,9 +164,9 @@ pub fn part2(guide: &StrategyGuide) -> PartOutput<usize> {
             response.score_shape() + outcome.score()
         })
         .collect();
-    PartOutput {
+    Ok(PartOutput {
         answer: scores.iter().sum(),
-    }
+    })
 }
 
 pub const DAY: Day<StrategyGuide, usize> = Day {
@@ -168,6 +179,11 @@ pub const DAY: Day<StrategyGuide, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day02.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day02.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
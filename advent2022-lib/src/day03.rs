@@ -2,24 +2,28 @@ use std::collections::HashSet;
 use std::fmt::Debug;
 use std::str::FromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::parser::parse_lines;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Item(usize);
 
-impl From<char> for Item {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Item {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         let mut value = c as u32;
-        if c.is_lowercase() {
+        if c.is_ascii_lowercase() {
             const OFFSET_A: u32 = 'a' as u32;
             value = value - OFFSET_A + 1
-        } else if c.is_uppercase() {
+        } else if c.is_ascii_uppercase() {
             const OFFSET_A: u32 = 'A' as u32;
             value = value - OFFSET_A + 27
         } else {
-            unreachable!();
+            return Err(ParseError::Str(format!("invalid item {c:?}")));
         }
-        Self(usize::try_from(value).unwrap())
+        Ok(Self(value as usize))
     }
 }
 
@@ -44,8 +48,17 @@ impl FromStr for RuckSack {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let items: Vec<Item> = s.chars().map(Item::from).collect();
-        let (left, right) = items.split_at(items.len().checked_div(2).unwrap());
+        let items: Vec<Item> = s
+            .char_indices()
+            .map(|(idx, c)| Item::try_from(c).map_err(|e| e.locate(s, &s[idx..idx + c.len_utf8()])))
+            .collect::<Result<_, _>>()?;
+        if items.len() % 2 != 0 {
+            return Err(ParseError::Str(format!(
+                "rucksack with {} items cannot be split into two equal compartments",
+                items.len()
+            )));
+        }
+        let (left, right) = items.split_at(items.len() / 2);
         Ok(RuckSack(left.to_vec(), right.to_vec()))
     }
 }
@@ -57,8 +70,7 @@ impl FromStr for RuckSacks {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let sacks: Result<Vec<RuckSack>, _> = s.lines().map(RuckSack::from_str).collect();
-        Ok(RuckSacks(sacks?))
+        Ok(RuckSacks(parse_lines(s)?))
     }
 }
 
@@ -66,47 +78,72 @@ pub fn parse(input: &str) -> ParseResult<RuckSacks> {
     input.parse()
 }
 
-pub fn part1(sacks: &RuckSacks) -> PartOutput<usize> {
-    let items_in_both_compartments = sacks.0.iter().map(|sack| {
-        let left: HashSet<_> = sack.0.iter().collect();
-        let right: HashSet<_> = sack.1.iter().collect();
-        let mut intersection = left.intersection(&right).copied();
-        let retval = if let Some(intersect) = intersection.next() {
-            intersect.clone()
-        } else {
-            log::error!("{left:?} {right:?}");
-            panic!();
-        };
-        assert!(intersection.next().is_none());
-        retval
-    });
-    PartOutput {
-        answer: items_in_both_compartments.map(|item| item.0).sum(),
-    }
+pub fn part1(sacks: &RuckSacks, _params: &()) -> SolveResult<PartOutput<usize>> {
+    let items_in_both_compartments = sacks
+        .0
+        .iter()
+        .enumerate()
+        .map(|(idx, sack)| {
+            let left: HashSet<_> = sack.0.iter().collect();
+            let right: HashSet<_> = sack.1.iter().collect();
+            let intersection: Vec<_> = left.intersection(&right).copied().collect();
+            match intersection[..] {
+                [intersect] => Ok(intersect.clone()),
+                _ => {
+                    log::error!("{left:?} {right:?}");
+                    Err(SolveError::NoSolution(format!(
+                        "rucksack {} has {} item types in both compartments, expected exactly 1",
+                        idx + 1,
+                        intersection.len()
+                    )))
+                },
+            }
+        })
+        .collect::<SolveResult<Vec<Item>>>()?;
+    Ok(PartOutput {
+        answer: items_in_both_compartments.iter().map(|item| item.0).sum(),
+    })
 }
 
-pub fn part2(sacks: &RuckSacks) -> PartOutput<usize> {
-    let badges = sacks.0.chunks_exact(3).map(|elf_group| {
-        let intersection = elf_group
-            .iter()
-            .fold(None, |acc: Option<HashSet<_>>, items| {
-                let items: HashSet<_> = items.0.iter().chain(items.1.iter()).collect();
-                Some(if let Some(intersection) = acc {
-                    intersection.intersection(&items).copied().collect()
-                } else {
-                    items
-                })
-            })
-            .unwrap();
-        if intersection.len() != 1 {
-            log::error!("{elf_group:?} -> {intersection:?}");
-            panic!();
-        }
-        intersection.into_iter().next().unwrap()
-    });
-    PartOutput {
-        answer: badges.map(|item| item.0).sum(),
+pub fn part2(sacks: &RuckSacks, _params: &()) -> SolveResult<PartOutput<usize>> {
+    if sacks.0.len() % 3 != 0 {
+        return Err(SolveError::Unsupported(format!(
+            "{} rucksacks cannot be split into groups of three elves",
+            sacks.0.len()
+        )));
     }
+    let badges = sacks
+        .0
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(idx, elf_group)| {
+            let intersection = elf_group
+                .iter()
+                .fold(None, |acc: Option<HashSet<_>>, items| {
+                    let items: HashSet<_> = items.0.iter().chain(items.1.iter()).collect();
+                    Some(if let Some(intersection) = acc {
+                        intersection.intersection(&items).copied().collect()
+                    } else {
+                        items
+                    })
+                })
+                .unwrap_or_default();
+            match intersection.iter().collect::<Vec<_>>()[..] {
+                [badge] => Ok((*badge).clone()),
+                _ => {
+                    log::error!("{elf_group:?} -> {intersection:?}");
+                    Err(SolveError::NoSolution(format!(
+                        "elf group {} has {} common item types, expected exactly 1 badge",
+                        idx + 1,
+                        intersection.len()
+                    )))
+                },
+            }
+        })
+        .collect::<SolveResult<Vec<Item>>>()?;
+    Ok(PartOutput {
+        answer: badges.iter().map(|item| item.0).sum(),
+    })
 }
 
 pub const DAY: Day<RuckSacks, usize> = Day {
@@ -119,6 +156,140 @@ pub const DAY: Day<RuckSacks, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day03.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day03.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+    use test_log::test;
+
+    use super::*;
+
+    const ITEM_TYPES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    fn priority(item: char) -> usize {
+        ITEM_TYPES.find(item).unwrap() + 1
+    }
+
+    /// The item types in both, comparing every pair of items
+    fn common(left: &str, right: &str) -> String {
+        let mut common = String::new();
+        for l in left.chars() {
+            for r in right.chars() {
+                if l == r && !common.contains(l) {
+                    common.push(l);
+                }
+            }
+        }
+        common
+    }
+
+    fn single_priority(common: &str) -> Option<usize> {
+        let mut chars = common.chars();
+        match (chars.next(), chars.next()) {
+            (Some(item), None) => Some(priority(item)),
+            _ => None,
+        }
+    }
+
+    /// Both answers, or `None` where a rucksack or group does not share exactly one type
+    fn brute_force(sacks: &[String]) -> (Option<usize>, Option<usize>) {
+        let part1 = sacks
+            .iter()
+            .map(|sack| {
+                let (left, right) = sack.split_at(sack.len() / 2);
+                single_priority(&common(left, right))
+            })
+            .sum();
+        let groups = sacks.chunks_exact(3);
+        let part2 = if groups.remainder().is_empty() {
+            groups
+                .map(|group| single_priority(&common(&common(&group[0], &group[1]), &group[2])))
+                .sum()
+        } else {
+            None
+        };
+        (part1, part2)
+    }
+
+    /// Three rucksacks whose compartments share exactly one item type each, and that share
+    /// exactly one badge, drawing every other item from types private to one compartment
+    fn valid_group() -> impl Strategy<Value = Vec<String>> {
+        let types = Just(ITEM_TYPES.chars().collect::<Vec<_>>()).prop_shuffle();
+        let elves = prop::collection::vec(
+            (any::<bool>(), prop::collection::vec(any::<Index>(), 0..6)),
+            3,
+        );
+        (types, elves).prop_map(|(types, elves)| {
+            let badge = types[0];
+            elves
+                .iter()
+                .enumerate()
+                .map(|(elf, (badge_on_left, picks))| {
+                    let shared = types[1 + elf];
+                    let left_pool = &types[4 + elf * 16..12 + elf * 16];
+                    let right_pool = &types[12 + elf * 16..20 + elf * 16];
+                    let mut left: String = std::iter::once(shared)
+                        .chain(picks.iter().map(|pick| *pick.get(left_pool)))
+                        .collect();
+                    let mut right: String = std::iter::once(shared)
+                        .chain(picks.iter().rev().map(|pick| *pick.get(right_pool)))
+                        .collect();
+                    if *badge_on_left {
+                        left.push(badge);
+                        right.push(shared);
+                    } else {
+                        left.push(shared);
+                        right.push(badge);
+                    }
+                    left + &right
+                })
+                .collect()
+        })
+    }
+
+    fn solve(sacks: &[String]) -> (Option<usize>, Option<usize>) {
+        let sacks = parse(&sacks.join("\n")).unwrap();
+        (
+            part1(&sacks, &()).ok().map(|output| output.answer),
+            part2(&sacks, &()).ok().map(|output| output.answer),
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_valid_groups_match_brute_force(
+            groups in prop::collection::vec(valid_group(), 1..4)
+        ) {
+            let sacks = groups.concat();
+            let expected = brute_force(&sacks);
+            prop_assert!(expected.0.is_some() && expected.1.is_some());
+            prop_assert_eq!(solve(&sacks), expected);
+        }
+
+        #[test]
+        fn test_random_sacks_match_brute_force(
+            sacks in prop::collection::vec(
+                prop::collection::vec(("[a-dA-B]", "[a-dA-B]"), 1..5),
+                1..7,
+            )
+        ) {
+            let sacks: Vec<String> = sacks
+                .iter()
+                .map(|items| {
+                    let (left, right): (String, String) = items.iter().cloned().unzip();
+                    left + &right
+                })
+                .collect();
+            prop_assert_eq!(solve(&sacks), brute_force(&sacks));
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::str::FromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::parser::parse_lines;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveResult};
 
 #[derive(Debug)]
 pub struct SectionAssignmentRange(usize, usize);
@@ -10,7 +12,13 @@ impl FromStr for SectionAssignmentRange {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some((left, right)) = s.split_once('-') {
-            Ok(Self(left.parse()?, right.parse()?))
+            Ok(Self(
+                left.parse()
+                    .map_err(|e| ParseError::from(e).locate(s, left))?,
+                right
+                    .parse()
+                    .map_err(|e| ParseError::from(e).locate(s, right))?,
+            ))
         } else {
             Err(ParseError::Str(format!("Cannot split {s} by '-'")))
         }
@@ -49,7 +57,10 @@ impl FromStr for SectionAssignmentPair {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some((left, right)) = s.split_once(',') {
-            Ok(Self(left.parse()?, right.parse()?))
+            Ok(Self(
+                left.parse().map_err(|e: ParseError| e.locate(s, left))?,
+                right.parse().map_err(|e: ParseError| e.locate(s, right))?,
+            ))
         } else {
             Err(ParseError::Str(format!("Cannot split {s} by ','")))
         }
@@ -63,9 +74,7 @@ impl FromStr for SectionAssignments {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines().map(|s| s.parse()).collect::<Result<_, _>>()?,
-        ))
+        Ok(Self(parse_lines(s)?))
     }
 }
 
@@ -73,24 +82,30 @@ pub fn parse(input: &str) -> ParseResult<SectionAssignments> {
     input.parse()
 }
 
-pub fn part1(section_assignments: &SectionAssignments) -> PartOutput<usize> {
-    PartOutput {
+pub fn part1(
+    section_assignments: &SectionAssignments,
+    _params: &(),
+) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
         answer: section_assignments
             .0
             .iter()
             .filter(|assignment| assignment.full_overlap())
             .count(),
-    }
+    })
 }
 
-pub fn part2(section_assignments: &SectionAssignments) -> PartOutput<usize> {
-    PartOutput {
+pub fn part2(
+    section_assignments: &SectionAssignments,
+    _params: &(),
+) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
         answer: section_assignments
             .0
             .iter()
             .filter(|assignment| assignment.overlap())
             .count(),
-    }
+    })
 }
 
 pub const DAY: Day<SectionAssignments, usize> = Day {
@@ -103,16 +118,54 @@ pub const DAY: Day<SectionAssignments, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day04.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day04.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
     use test_log::test;
 
     use super::*;
 
+    /// A range of sections, small enough that touching and equal ranges are common
+    fn range() -> impl Strategy<Value = (usize, usize)> {
+        (0..12usize, 0..6usize).prop_map(|(start, len)| (start, start + len))
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force(pairs in prop::collection::vec((range(), range()), 1..20)) {
+            let input = pairs
+                .iter()
+                .map(|((a, b), (c, d))| format!("{a}-{b},{c}-{d}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let sections = |(start, end): (usize, usize)| (start..=end).collect::<HashSet<_>>();
+            let (mut contained, mut overlapping) = (0, 0);
+            for &(left, right) in &pairs {
+                let (left, right) = (sections(left), sections(right));
+                if left.is_subset(&right) || right.is_subset(&left) {
+                    contained += 1;
+                }
+                if !left.is_disjoint(&right) {
+                    overlapping += 1;
+                }
+            }
+            let assignments = parse(&input).unwrap();
+            prop_assert_eq!(part1(&assignments, &()).unwrap().answer, contained);
+            prop_assert_eq!(part2(&assignments, &()).unwrap().answer, overlapping);
+        }
+    }
+
     #[test]
     fn test_full_overlap() {
         let assignment_pair =
@@ -3,7 +3,9 @@ use std::str::FromStr;
 use recap::Recap;
 use serde::Deserialize;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::parser::parse_lines;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
 
 #[derive(Debug)]
 pub struct StacksAndProcedure {
@@ -18,10 +20,23 @@ impl FromStr for StacksAndProcedure {
         let (stacks, procedure) = s
             .split_once("\n\n")
             .ok_or(ParseError::Str(String::from("malformed input string")))?;
-        Ok(Self {
-            stacks: stacks.parse()?,
-            procedure: procedure.parse()?,
-        })
+        let (stacks_str, procedure_str) = (stacks, procedure);
+        let stacks: Stacks = stacks_str
+            .parse()
+            .map_err(|e: ParseError| e.locate(s, stacks_str))?;
+        let procedure: Procedure = procedure_str
+            .parse()
+            .map_err(|e: ParseError| e.locate(s, procedure_str))?;
+        for (line, step) in procedure_str.lines().zip(&procedure.0) {
+            for stack in [step.source, step.destination] {
+                if !(1..=stacks.0.len()).contains(&stack) {
+                    return Err(
+                        ParseError::Str(format!("stack {stack} does not exist")).locate(s, line)
+                    );
+                }
+            }
+        }
+        Ok(Self { stacks, procedure })
     }
 }
 
@@ -29,19 +44,43 @@ impl FromStr for StacksAndProcedure {
 pub struct Stacks(Vec<Vec<char>>);
 
 impl Stacks {
-    pub fn crate_mover_9000(&mut self, step: &ProcedureStep) {
+    fn check_quantity(&self, step: &ProcedureStep) -> SolveResult<()> {
+        let available = self.0[step.source - 1].len();
+        if available < step.quantity {
+            return Err(SolveError::Unsupported(format!(
+                "cannot move {} crates from stack {} holding {available}",
+                step.quantity, step.source
+            )));
+        }
+        Ok(())
+    }
+    pub fn crate_mover_9000(&mut self, step: &ProcedureStep) -> SolveResult<()> {
+        self.check_quantity(step)?;
         for _ in 0..step.quantity {
-            let popped = self.0[step.source - 1].pop().unwrap();
-            self.0[step.destination - 1].push(popped);
+            if let Some(popped) = self.0[step.source - 1].pop() {
+                self.0[step.destination - 1].push(popped);
+            }
         }
+        Ok(())
     }
-    pub fn crate_mover_9001(&mut self, step: &ProcedureStep) {
+    pub fn crate_mover_9001(&mut self, step: &ProcedureStep) -> SolveResult<()> {
+        self.check_quantity(step)?;
         let source = &mut self.0[step.source - 1];
-        let final_length = source.len().checked_sub(step.quantity).unwrap();
-        let popped = source.split_off(final_length);
+        let popped = source.split_off(source.len() - step.quantity);
         for el in popped {
             self.0[step.destination - 1].push(el);
         }
+        Ok(())
+    }
+    fn tops(&self) -> SolveResult<String> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(idx, s)| {
+                s.last()
+                    .ok_or_else(|| SolveError::NoSolution(format!("stack {} is empty", idx + 1)))
+            })
+            .collect()
     }
 }
 
@@ -49,7 +88,18 @@ impl FromStr for Stacks {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let num_stacks = s.lines().last().unwrap().split_whitespace().count();
+        let footer = s.lines().last().unwrap_or_default();
+        let num_stacks = footer
+            .split_whitespace()
+            .map(|num| {
+                num.parse::<usize>()
+                    .map_err(|e| ParseError::from(e).locate(s, num))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .len();
+        if num_stacks == 0 {
+            return Err(ParseError::Str(String::from("missing stack numbers")).locate(s, footer));
+        }
         let mut stacks = vec![Vec::new(); num_stacks];
         for line in s.lines().rev().skip(1) {
             for (idx, stack) in stacks.iter_mut().enumerate().take(num_stacks) {
@@ -72,9 +122,7 @@ impl FromStr for Procedure {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines().map(|l| l.parse()).collect::<Result<_, _>>()?,
-        ))
+        Ok(Self(parse_lines(s)?))
     }
 }
 
@@ -90,25 +138,25 @@ pub fn parse(input: &str) -> ParseResult<StacksAndProcedure> {
     input.parse()
 }
 
-pub fn part1(input: &StacksAndProcedure) -> PartOutput<String> {
+pub fn part1(input: &StacksAndProcedure, _params: &()) -> SolveResult<PartOutput<String>> {
     let mut stacks = input.stacks.clone();
     for step in &input.procedure.0 {
         log::debug!("stacks: {stacks:?}");
-        stacks.crate_mover_9000(step)
-    }
-    PartOutput {
-        answer: stacks.0.iter().map(|s| s.last().unwrap()).collect(),
+        stacks.crate_mover_9000(step)?;
     }
+    Ok(PartOutput {
+        answer: stacks.tops()?,
+    })
 }
 
-pub fn part2(input: &StacksAndProcedure) -> PartOutput<String> {
+pub fn part2(input: &StacksAndProcedure, _params: &()) -> SolveResult<PartOutput<String>> {
     let mut stacks = input.stacks.clone();
     for step in &input.procedure.0 {
-        stacks.crate_mover_9001(step)
-    }
-    PartOutput {
-        answer: stacks.0.iter().map(|s| s.last().unwrap()).collect(),
+        stacks.crate_mover_9001(step)?;
     }
+    Ok(PartOutput {
+        answer: stacks.tops()?,
+    })
 }
 
 pub const DAY: Day<StacksAndProcedure, String> = Day {
@@ -121,8 +169,13 @@ pub const DAY: Day<StacksAndProcedure, String> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day05.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day05.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
 use std::collections::HashSet;
 use std::str::FromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
 
 #[derive(Debug)]
 pub struct Signal(Vec<char>);
@@ -10,6 +11,13 @@ impl FromStr for Signal {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_end();
+        if let Some((idx, c)) = s.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(
+                ParseError::Str(format!("unexpected character {c:?} in signal"))
+                    .locate(s, &s[idx..idx + c.len_utf8()]),
+            );
+        }
         Ok(Self(s.chars().collect()))
     }
 }
@@ -18,25 +26,27 @@ pub fn parse(input: &str) -> ParseResult<Signal> {
     input.parse()
 }
 
-fn marker(signal: &Signal, window_size: usize) -> usize {
-    window_size
-        + signal
-            .0
-            .windows(window_size)
-            .position(|window| window.len() == window.iter().collect::<HashSet<_>>().len())
-            .unwrap()
+fn marker(signal: &Signal, window_size: usize) -> SolveResult<usize> {
+    let position = signal
+        .0
+        .windows(window_size)
+        .position(|window| window.len() == window.iter().collect::<HashSet<_>>().len())
+        .ok_or_else(|| {
+            SolveError::NoSolution(format!("no marker of {window_size} different characters"))
+        })?;
+    Ok(window_size + position)
 }
 
-pub fn part1(signal: &Signal) -> PartOutput<usize> {
-    PartOutput {
-        answer: marker(signal, 4),
-    }
+pub fn part1(signal: &Signal, _params: &()) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
+        answer: marker(signal, 4)?,
+    })
 }
 
-pub fn part2(signal: &Signal) -> PartOutput<usize> {
-    PartOutput {
-        answer: marker(signal, 14),
-    }
+pub fn part2(signal: &Signal, _params: &()) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
+        answer: marker(signal, 14)?,
+    })
 }
 
 pub const DAY: Day<Signal, usize> = Day {
@@ -49,6 +59,41 @@ pub const DAY: Day<Signal, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day06.in.txt"),
+    examples: &[Example::new("main", include_str!("../../examples/day06.in.txt"))],
+    params: NO_PARAMS,
 };
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use super::*;
+
+    /// The number of characters up to the end of the first `size` that all differ, comparing
+    /// every pair in every window
+    fn brute_force_marker(signal: &[char], size: usize) -> Option<usize> {
+        (size..=signal.len()).find(|&end| {
+            let window = &signal[end - size..end];
+            (0..size).all(|i| (i + 1..size).all(|j| window[i] != window[j]))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force(signal in "[a-p]{1,40}") {
+            let chars: Vec<char> = signal.chars().collect();
+            let parsed = parse(&signal).unwrap();
+            prop_assert_eq!(
+                part1(&parsed, &()).ok().map(|output| output.answer),
+                brute_force_marker(&chars, 4)
+            );
+            prop_assert_eq!(
+                part2(&parsed, &()).ok().map(|output| output.answer),
+                brute_force_marker(&chars, 14)
+            );
+        }
+    }
+}
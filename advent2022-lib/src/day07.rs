@@ -3,7 +3,8 @@ use std::fmt::Debug;
 use std::rc::{Rc, Weak};
 use std::str::FromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
 
 #[derive(Debug)]
 struct Commands(Vec<Command>);
@@ -12,15 +13,27 @@ impl FromStr for Commands {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s
-            .strip_prefix("$ ")
-            .ok_or(ParseError::Str(String::from("invalid input")))?;
+        let first_line = s.lines().next().unwrap_or_default();
+        let commands_str = s.strip_prefix("$ ").ok_or_else(|| {
+            ParseError::Str(String::from("expected a command starting with '$ '"))
+                .locate(s, first_line)
+        })?;
         let commands = Self(
-            s.split("\n$ ")
-                .map(FromStr::from_str)
+            commands_str
+                .split("\n$ ")
+                .map(|command| {
+                    command
+                        .parse()
+                        .map_err(|e: ParseError| e.locate(s, command))
+                })
                 .collect::<Result<_, _>>()?,
         );
-        assert!(matches!(commands.0.first().unwrap(), Command::CdRoot));
+        if !matches!(commands.0.first(), Some(Command::CdRoot)) {
+            return Err(
+                ParseError::Str(String::from("expected the first command to be 'cd /'"))
+                    .locate(s, first_line),
+            );
+        }
         Ok(commands)
     }
 }
@@ -37,25 +50,25 @@ impl FromStr for Command {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let command = s.lines().next().unwrap().strip_prefix("$ ").unwrap_or(s);
+        let command = s.lines().next().unwrap_or_default();
         if command == "cd /" {
             return Ok(Self::CdRoot);
         }
         if command == "cd .." {
             return Ok(Self::CdParent);
         }
-        if let Some(cd) = s.strip_prefix("cd ") {
+        if let Some(cd) = command.strip_prefix("cd ") {
             return Ok(Self::CdChild(cd.to_owned()));
         }
-        if let Some("ls") = command.lines().next() {
+        if command == "ls" {
             return Ok(Self::Ls(
                 s.lines()
                     .skip(1)
-                    .map(|line| line.parse())
+                    .map(|line| line.parse().map_err(|e: ParseError| e.locate(s, line)))
                     .collect::<Result<_, _>>()?,
             ));
         }
-        Err(ParseError::Str(format!("unrecognized command {s}")))
+        Err(ParseError::Str(format!("unrecognized command {command}")).locate(s, command))
     }
 }
 
@@ -82,7 +95,9 @@ impl FromStr for DirChild {
             Self::Dir(right.to_owned())
         } else {
             Self::File(File {
-                size: left.parse()?,
+                size: left
+                    .parse()
+                    .map_err(|e| ParseError::from(e).locate(s, left))?,
                 name: right.to_owned(),
             })
         })
@@ -178,7 +193,14 @@ fn parse(input: &str) -> ParseResult<DirNode> {
     for command in commands.0.iter().skip(1) {
         match command {
             Command::CdRoot => current_path.truncate(1),
-            Command::CdParent => current_path.truncate(current_path.len() - 1),
+            Command::CdParent => {
+                if current_path.len() == 1 {
+                    return Err(ParseError::Str(String::from(
+                        "cannot 'cd ..' above the root directory",
+                    )));
+                }
+                current_path.truncate(current_path.len() - 1)
+            },
             Command::CdChild(name) => {
                 let last_part = current_path.last().unwrap().clone();
                 let rc_last_part = last_part.upgrade().unwrap();
@@ -205,10 +227,10 @@ fn parse(input: &str) -> ParseResult<DirNode> {
     Ok(root)
 }
 
-pub fn part1(root: &DirNode) -> PartOutput<usize> {
+pub fn part1(root: &DirNode, _params: &()) -> SolveResult<PartOutput<usize>> {
     let dir_sizes = root.dir_sizes();
     log::info!("Directory Sizes: {dir_sizes:?}");
-    PartOutput {
+    Ok(PartOutput {
         answer: dir_sizes
             .iter()
             .filter_map(
@@ -221,15 +243,19 @@ pub fn part1(root: &DirNode) -> PartOutput<usize> {
                 },
             )
             .sum(),
-    }
+    })
 }
 
-pub fn part2(root: &DirNode) -> PartOutput<usize> {
+pub fn part2(root: &DirNode, _params: &()) -> SolveResult<PartOutput<usize>> {
     const TOTAL_SPACE: usize = 70000000;
     const REQUIRED_UNUSED_SPACE: usize = 30000000;
     let used_space = root.size();
-    let unused_space = TOTAL_SPACE - used_space;
-    let space_to_delete = REQUIRED_UNUSED_SPACE - unused_space;
+    let unused_space = TOTAL_SPACE.checked_sub(used_space).ok_or_else(|| {
+        SolveError::Unsupported(format!(
+            "{used_space} used exceeds the total disk space of {TOTAL_SPACE}"
+        ))
+    })?;
+    let space_to_delete = REQUIRED_UNUSED_SPACE.saturating_sub(unused_space);
 
     let mut dir_sizes: Vec<usize> = root
         .dir_sizes()
@@ -240,11 +266,15 @@ pub fn part2(root: &DirNode) -> PartOutput<usize> {
     let size_to_delete = dir_sizes
         .into_iter()
         .find(|s| *s >= space_to_delete)
-        .unwrap();
+        .ok_or_else(|| {
+            SolveError::NoSolution(format!(
+                "no directory is large enough to free up {space_to_delete}"
+            ))
+        })?;
 
-    PartOutput {
+    Ok(PartOutput {
         answer: size_to_delete,
-    }
+    })
 }
 
 pub const DAY: Day<DirNode, usize> = Day {
@@ -257,6 +287,11 @@ pub const DAY: Day<DirNode, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day07.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day07.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use crate::{Day, DayCalc, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveResult};
 
 #[derive(Debug)]
 pub struct Forest(HashMap<(usize, usize), usize>);
@@ -84,23 +85,26 @@ impl Forest {
 pub fn parse(input: &str) -> ParseResult<Forest> {
     let mut forest = HashMap::new();
     for (row_idx, line) in input.lines().enumerate() {
-        for (col_idx, c) in line.chars().enumerate() {
-            forest.insert((row_idx + 1, col_idx + 1), c.to_string().parse()?);
+        for (col_idx, (byte_idx, c)) in line.char_indices().enumerate() {
+            let height = c.to_string().parse().map_err(|e| {
+                ParseError::from(e).locate(input, &line[byte_idx..byte_idx + c.len_utf8()])
+            })?;
+            forest.insert((row_idx + 1, col_idx + 1), height);
         }
     }
     Ok(Forest(forest))
 }
 
-pub fn part1(forest: &Forest) -> PartOutput<usize> {
-    PartOutput {
+pub fn part1(forest: &Forest, _params: &()) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
         answer: forest.count_visible(),
-    }
+    })
 }
 
-pub fn part2(forest: &Forest) -> PartOutput<usize> {
-    PartOutput {
+pub fn part2(forest: &Forest, _params: &()) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
         answer: forest.max_scenic_score(),
-    }
+    })
 }
 
 pub const DAY: Day<Forest, usize> = Day {
@@ -113,6 +117,76 @@ pub const DAY: Day<Forest, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day08.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day08.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use super::*;
+
+    /// The trees seen looking out from a tree in each direction, nearest first
+    fn lines_of_sight(grid: &[Vec<usize>], row: usize, col: usize) -> [Vec<usize>; 4] {
+        let column: Vec<usize> = grid.iter().map(|line| line[col]).collect();
+        [
+            column[..row].iter().rev().copied().collect(),
+            column[row + 1..].to_vec(),
+            grid[row][..col].iter().rev().copied().collect(),
+            grid[row][col + 1..].to_vec(),
+        ]
+    }
+
+    fn brute_force(grid: &[Vec<usize>]) -> (usize, usize) {
+        let (mut visible, mut best_score) = (0, 0);
+        for (row, line) in grid.iter().enumerate() {
+            for (col, &height) in line.iter().enumerate() {
+                let lines = lines_of_sight(grid, row, col);
+                if lines
+                    .iter()
+                    .any(|line| line.iter().all(|&tree| tree < height))
+                {
+                    visible += 1;
+                }
+                let score: usize = lines
+                    .iter()
+                    .map(|line| match line.iter().position(|&tree| tree >= height) {
+                        Some(blocked) => blocked + 1,
+                        None => line.len(),
+                    })
+                    .product();
+                best_score = best_score.max(score);
+            }
+        }
+        (visible, best_score)
+    }
+
+    /// A rectangular grid of few heights, so that trees of equal height are common
+    fn grid() -> impl Strategy<Value = Vec<Vec<usize>>> {
+        (1..8usize, 1..8usize).prop_flat_map(|(rows, cols)| {
+            prop::collection::vec(prop::collection::vec(0..4usize, cols), rows)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force(grid in grid()) {
+            let input = grid
+                .iter()
+                .map(|line| line.iter().map(ToString::to_string).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let (visible, best_score) = brute_force(&grid);
+            let forest = parse(&input).unwrap();
+            prop_assert_eq!(part1(&forest, &()).unwrap().answer, visible);
+            prop_assert_eq!(part2(&forest, &()).unwrap().answer, best_score);
+        }
+    }
+}
@@ -2,7 +2,20 @@ use std::collections::HashSet;
 use std::ops::Sub;
 use std::str::FromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::DayParams;
+use crate::parser::parse_lines;
+use crate::{
+    params, Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult,
+};
+
+params! {
+    pub struct Params {
+        /// How many knots follow the head of the rope in part 1
+        pub part1_tails: usize,
+        /// How many knots follow the head of the longer rope in part 2
+        pub part2_tails: usize,
+    }
+}
 
 #[derive(Debug)]
 pub enum Direction {
@@ -40,8 +53,10 @@ impl FromStr for Motion {
             .split_once(' ')
             .ok_or(ParseError::Str(format!("unknown motion {s}")))?;
         Ok(Motion {
-            direction: left.parse()?,
-            distance: right.parse()?,
+            direction: left.parse().map_err(|e: ParseError| e.locate(s, left))?,
+            distance: right
+                .parse()
+                .map_err(|e| ParseError::from(e).locate(s, right))?,
         })
     }
 }
@@ -53,9 +68,7 @@ impl FromStr for Motions {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines().map(|l| l.parse()).collect::<Result<_, _>>()?,
-        ))
+        Ok(Self(parse_lines(s)?))
     }
 }
 
@@ -103,12 +116,12 @@ impl Pos {
         }
     }
 
-    /// a tail chasing a head
+    /// a tail chasing a head, stepping one square towards it once they no longer touch.
+    ///
+    /// The head only ever moves one square at a time, so the tail is never left more than one
+    /// step behind.
     fn chase(&self, head: &Pos) -> Self {
         let diff = head - self;
-        if diff.x.abs() > 2 || diff.y.abs() > 2 {
-            panic!("{head:?} {self:?}")
-        }
         if diff.x.abs() > 1 || diff.y.abs() > 1 {
             Self {
                 x: self.x + diff.x.signum(),
@@ -149,21 +162,32 @@ fn calc_tails(motions: &Motions, num_tails: usize) -> Vec<HashSet<Pos>> {
     tails.into_iter().map(|tail| tail.visited).collect()
 }
 
-pub fn part1(motions: &Motions) -> PartOutput<usize> {
-    let tail_visited = calc_tails(motions, 1).pop().unwrap();
-    PartOutput {
-        answer: tail_visited.len(),
-    }
+/// How many positions the last of `num_tails` knots visits
+fn last_tail_visited(motions: &Motions, num_tails: usize) -> SolveResult<usize> {
+    calc_tails(motions, num_tails)
+        .pop()
+        .map(|visited| visited.len())
+        .ok_or_else(|| SolveError::Unsupported(String::from("a rope needs at least one tail")))
 }
 
-pub fn part2(motions: &Motions) -> PartOutput<usize> {
-    let tail_visited = calc_tails(motions, 9).pop().unwrap();
-    PartOutput {
-        answer: tail_visited.len(),
-    }
+pub fn part1(motions: &Motions, params: &Params) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
+        answer: last_tail_visited(motions, params.part1_tails)?,
+    })
+}
+
+pub fn part2(motions: &Motions, params: &Params) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
+        answer: last_tail_visited(motions, params.part2_tails)?,
+    })
 }
 
-pub const DAY: Day<Motions, usize> = Day {
+const PARAMS: Params = Params {
+    part1_tails: 1,
+    part2_tails: 9,
+};
+
+pub const DAY: Day<Motions, usize, Params> = Day {
     title: "Rope Bridge",
     display: (
         "The tail of the rope visits {answer} positions at least once",
@@ -173,6 +197,78 @@ pub const DAY: Day<Motions, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
+    },
+    examples: &[
+        Example::new("main", include_str!("../../examples/day09.in.txt")),
+        Example::new("larger", include_str!("../../examples/day09-larger.in.txt")),
+    ],
+    params: DayParams {
+        input: PARAMS,
+        example: PARAMS,
     },
-    example: include_str!("../../examples/day09.in.txt"),
 };
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use super::*;
+
+    /// Every position the last knot of a rope of `knots` knots visits, moving the knots as
+    /// plain coordinates one step of the head at a time
+    fn brute_force(motions: &[(char, usize)], knots: usize) -> usize {
+        let mut rope = vec![(0i32, 0i32); knots];
+        let mut visited = vec![(0, 0)];
+        for &(direction, distance) in motions {
+            let (dx, dy) = match direction {
+                'U' => (0, -1),
+                'D' => (0, 1),
+                'L' => (-1, 0),
+                _ => (1, 0),
+            };
+            for _ in 0..distance {
+                rope[0] = (rope[0].0 + dx, rope[0].1 + dy);
+                for knot in 1..knots {
+                    let (ahead, behind) = (rope[knot - 1], rope[knot]);
+                    if (ahead.0 - behind.0).abs() > 1 || (ahead.1 - behind.1).abs() > 1 {
+                        rope[knot] = (
+                            behind.0 + (ahead.0 - behind.0).signum(),
+                            behind.1 + (ahead.1 - behind.1).signum(),
+                        );
+                    }
+                }
+                if !visited.contains(&rope[knots - 1]) {
+                    visited.push(rope[knots - 1]);
+                }
+            }
+        }
+        visited.len()
+    }
+
+    #[test]
+    fn test_chase() {
+        let tail = Pos::default();
+        assert_eq!(tail.chase(&Pos { x: 1, y: 1 }), tail);
+        assert_eq!(tail.chase(&Pos { x: 2, y: 1 }), Pos { x: 1, y: 1 });
+        // a head further away than a rope allows is still chased one step at a time
+        assert_eq!(tail.chase(&Pos { x: -5, y: 0 }), Pos { x: -1, y: 0 });
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force(
+            motions in prop::collection::vec((prop::sample::select(vec!['U', 'D', 'L', 'R']), 1..8usize), 1..30)
+        ) {
+            let input = motions
+                .iter()
+                .map(|(direction, distance)| format!("{direction} {distance}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let parsed = parse(&input).unwrap();
+            prop_assert_eq!(part1(&parsed, &PARAMS).unwrap().answer, brute_force(&motions, 2));
+            prop_assert_eq!(part2(&parsed, &PARAMS).unwrap().answer, brute_force(&motions, 10));
+        }
+    }
+}
@@ -1,7 +1,19 @@
 use std::slice::Iter;
 use std::str::FromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::ocr::Image;
+use crate::params::DayParams;
+use crate::parser::parse_lines;
+use crate::{
+    params, Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult,
+};
+
+params! {
+    pub struct Params {
+        /// Whether part 2 reads the letters on the CRT, rather than answering with its image
+        pub read_letters: bool,
+    }
+}
 
 #[derive(Debug)]
 pub struct Program(Vec<Instruction>);
@@ -10,9 +22,7 @@ impl FromStr for Program {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines().map(FromStr::from_str).collect::<Result<_, _>>()?,
-        ))
+        Ok(Self(parse_lines(s)?))
     }
 }
 
@@ -28,7 +38,11 @@ impl FromStr for Instruction {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some((left, right)) = s.split_once(' ') {
             match left {
-                "addx" => Ok(Self::Addx(right.parse()?)),
+                "addx" => Ok(Self::Addx(
+                    right
+                        .parse()
+                        .map_err(|e| ParseError::from(e).locate(s, right))?,
+                )),
                 _ => Err(ParseError::Str(format!(
                     "unknown instruction {left} with operand {right}"
                 ))),
@@ -67,9 +81,14 @@ impl<'p> Cpu<'p> {
         }
     }
 
-    fn step(&mut self) {
+    fn step(&mut self) -> SolveResult<()> {
         if let Some(val) = self.curr_addx_instruction {
-            self.register += val;
+            self.register = self.register.checked_add(val).ok_or_else(|| {
+                SolveError::Unsupported(format!(
+                    "register overflowed during cycle {}",
+                    self.cycles + 1
+                ))
+            })?;
             self.curr_addx_instruction = None;
         } else {
             match self.pc.next() {
@@ -77,14 +96,20 @@ impl<'p> Cpu<'p> {
                 Some(Instruction::Addx(val)) => {
                     self.curr_addx_instruction = Some(*val);
                 },
-                None => unreachable!(),
+                None => {
+                    return Err(SolveError::NoSolution(format!(
+                        "program ended after {} cycles",
+                        self.cycles
+                    )))
+                },
             }
         }
         self.cycles += 1;
+        Ok(())
     }
 }
 
-pub fn part1(program: &Program) -> PartOutput<String> {
+pub fn part1(program: &Program, _params: &Params) -> SolveResult<PartOutput<String>> {
     let mut cpu = Cpu::new(1, program);
     let mut interesting_signal_strengths = Vec::new();
     for cycle in 1..=220 {
@@ -94,59 +119,78 @@ pub fn part1(program: &Program) -> PartOutput<String> {
                 // during means cycle is one behind
                 cpu.register
                     .checked_mul(isize::try_from(cpu.cycles + 1).unwrap())
-                    .unwrap(),
+                    .ok_or_else(|| {
+                        SolveError::Unsupported(format!(
+                            "signal strength overflowed during cycle {cycle}"
+                        ))
+                    })?,
             );
         }
-        cpu.step();
+        cpu.step()?;
     }
     assert_eq!(interesting_signal_strengths.len(), 6);
-    PartOutput {
+    Ok(PartOutput {
         answer: interesting_signal_strengths
             .into_iter()
-            .sum::<isize>()
+            .try_fold(0isize, |sum, strength| sum.checked_add(strength))
+            .ok_or_else(|| {
+                SolveError::Unsupported(String::from("sum of signal strengths overflowed"))
+            })?
             .to_string(),
-    }
+    })
 }
 
-struct Crt(Vec<String>);
-
-pub fn part2(program: &Program) -> PartOutput<String> {
+/// The image the CRT draws while running the program
+pub fn crt(program: &Program) -> SolveResult<Image> {
     let mut cpu = Cpu::new(1, program);
-    let mut crt = Crt(vec![String::new(); 6]);
+    let mut rows = vec![Vec::new(); 6];
     for cycle in 1..=240 {
         let cycle_idx: usize = cycle - 1;
         let row_idx = cycle_idx.checked_div_euclid(40).unwrap();
         let col_idx = cycle_idx.checked_rem_euclid(40).unwrap();
-        crt.0.get_mut(row_idx).unwrap().push(
-            if cpu
-                .register
-                .checked_sub(isize::try_from(col_idx).unwrap())
-                .unwrap()
-                .abs()
-                <= 1
-            {
-                '⬛'
-            } else {
-                '　'
-            },
-        );
-        cpu.step();
+        rows.get_mut(row_idx)
+            .unwrap()
+            .push(cpu.register.abs_diff(isize::try_from(col_idx).unwrap()) <= 1);
+        cpu.step()?;
     }
-    PartOutput {
-        answer: crt.0.join("\n"),
+    Ok(Image::new(rows))
+}
+
+pub fn part2(program: &Program, params: &Params) -> SolveResult<PartOutput<String>> {
+    let image = crt(program)?;
+    log::debug!("CRT image:\n{image}");
+    if !params.read_letters {
+        return Ok(PartOutput {
+            answer: image.to_string(),
+        });
     }
+    let answer = image.read_letters().map_err(|e| {
+        SolveError::Unsupported(format!("could not read the CRT image, {e}\n{image}"))
+    })?;
+    Ok(PartOutput { answer })
 }
 
-pub const DAY: Day<Program, String> = Day {
+pub const DAY: Day<Program, String, Params> = Day {
     title: "Cathode-Ray Tube",
     display: (
         "The sum of the six signal strengths is {answer}",
-        "The CRT image is:\n{answer}",
+        "The CRT shows:\n{answer}",
     ),
     calc: DayCalc {
         parse,
         part1,
         part2,
+        explain: None,
+    },
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day10.in.txt"),
+    )],
+    params: DayParams {
+        input: Params { read_letters: true },
+        // The example draws a test pattern rather than letters
+        example: Params {
+            read_letters: false,
+        },
     },
-    example: include_str!("../../examples/day10.in.txt"),
 };
@@ -6,7 +6,20 @@ use recap::Recap;
 use serde::Deserialize;
 use serde_with::DeserializeFromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::DayParams;
+use crate::parser::parse_blocks;
+use crate::{
+    params, Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult,
+};
+
+params! {
+    pub struct Params {
+        /// How many rounds to count in part 1, where worry is relieved after each inspection
+        pub part1_rounds: usize,
+        /// How many rounds to count in part 2, without relief
+        pub part2_rounds: usize,
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub struct Worry(usize);
@@ -34,13 +47,13 @@ pub enum Operation {
 }
 
 impl Operation {
-    fn apply(&self, other: &Worry) -> Worry {
+    fn apply(&self, other: &Worry) -> Option<Worry> {
         let other = other.0;
-        Worry(match self {
-            Operation::Mul(v) => other * v,
-            Operation::Add(v) => other + v,
-            Operation::Square => other * other,
-        })
+        Some(Worry(match self {
+            Operation::Mul(v) => other.checked_mul(*v)?,
+            Operation::Add(v) => other.checked_add(*v)?,
+            Operation::Square => other.checked_mul(other)?,
+        }))
     }
 }
 
@@ -48,7 +61,10 @@ impl FromStr for Operation {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once(' ').unwrap() {
+        let operation = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::Str(format!("operation not recognised: {s}")))?;
+        match operation {
             ("*", "old") => Ok(Self::Square),
             ("*", v) => Ok(Self::Mul(v.parse()?)),
             ("+", v) => Ok(Self::Add(v.parse()?)),
@@ -77,13 +93,21 @@ impl FromStr for Monkeys {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let monkeys = Self(
-            s.split("\n\n")
-                .map(FromStr::from_str)
-                .collect::<Result<_, _>>()?,
-        );
-        for (idx, monkey) in monkeys.0.iter().enumerate() {
-            assert_eq!(idx, monkey.idx)
+        let monkeys = Self(parse_blocks(s)?);
+        for ((idx, monkey), block) in monkeys.0.iter().enumerate().zip(s.split("\n\n")) {
+            let error = if idx != monkey.idx {
+                format!("expected monkey {idx}, found monkey {}", monkey.idx)
+            } else if monkey.divisible == 0 {
+                format!("monkey {idx} cannot test divisibility by 0")
+            } else if let Some(target) = [monkey.true_monkey, monkey.false_monkey]
+                .into_iter()
+                .find(|&target| target == idx || target >= monkeys.0.len())
+            {
+                format!("monkey {idx} cannot throw to monkey {target}")
+            } else {
+                continue;
+            };
+            return Err(ParseError::Str(error).locate(s, block));
         }
         Ok(monkeys)
     }
@@ -93,16 +117,29 @@ pub fn parse(input: &str) -> ParseResult<Monkeys> {
     input.parse()
 }
 
-fn get_monkey_business(monkeys: Monkeys, rounds: usize, damage_relief: bool) -> usize {
+fn get_monkey_business(monkeys: Monkeys, rounds: usize, damage_relief: bool) -> SolveResult<usize> {
     log::debug!("monkeys={monkeys:?}");
-    let least_common_multiple_divisor: usize = monkeys.0.iter().map(|m| m.divisible).product();
+    let least_common_multiple_divisor = monkeys
+        .0
+        .iter()
+        .try_fold(1usize, |acc, m| acc.checked_mul(m.divisible))
+        .ok_or_else(|| {
+            SolveError::Unsupported(String::from(
+                "the product of the divisibility tests overflows",
+            ))
+        })?;
     let monkeys: Vec<RefCell<Monkey>> = monkeys.0.into_iter().map(RefCell::new).collect();
     let mut inspections: Vec<usize> = vec![0; monkeys.len()];
     for round in 1..=rounds {
         for monkey in &monkeys {
             let mut monkey = monkey.borrow_mut();
             while let Some(to_inspect) = monkey.to_inspect.0.pop_front() {
-                let mut inspection = monkey.operation.apply(&to_inspect);
+                let mut inspection = monkey.operation.apply(&to_inspect).ok_or_else(|| {
+                    SolveError::Unsupported(format!(
+                        "worry level overflowed when monkey {} inspected {}",
+                        monkey.idx, to_inspect.0
+                    ))
+                })?;
                 if damage_relief {
                     inspection = Worry(inspection.0.checked_div(3).unwrap());
                 }
@@ -131,22 +168,34 @@ fn get_monkey_business(monkeys: Monkeys, rounds: usize, damage_relief: bool) ->
         log::debug!("Round {round} inspections={inspections:?}");
     }
     inspections.sort_unstable();
-    inspections.pop().unwrap() * inspections.pop().unwrap()
+    match inspections[..] {
+        [.., second, first] => first.checked_mul(second).ok_or_else(|| {
+            SolveError::Unsupported(String::from("the level of monkey business overflows"))
+        }),
+        _ => Err(SolveError::NoSolution(String::from(
+            "monkey business needs at least two monkeys",
+        ))),
+    }
 }
 
-pub fn part1(monkeys: &Monkeys) -> PartOutput<usize> {
-    PartOutput {
-        answer: get_monkey_business(monkeys.clone(), 20, true),
-    }
+pub fn part1(monkeys: &Monkeys, params: &Params) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
+        answer: get_monkey_business(monkeys.clone(), params.part1_rounds, true)?,
+    })
 }
 
-pub fn part2(monkeys: &Monkeys) -> PartOutput<usize> {
-    PartOutput {
-        answer: get_monkey_business(monkeys.clone(), 10000, false),
-    }
+pub fn part2(monkeys: &Monkeys, params: &Params) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
+        answer: get_monkey_business(monkeys.clone(), params.part2_rounds, false)?,
+    })
 }
 
-pub const DAY: Day<Monkeys, usize> = Day {
+const PARAMS: Params = Params {
+    part1_rounds: 20,
+    part2_rounds: 10000,
+};
+
+pub const DAY: Day<Monkeys, usize, Params> = Day {
     title: "Monkey in the Middle",
     display: (
         "The level of monkey business after 20 rounds of stuff-slinging simian shenanigans is {answer}",
@@ -156,6 +205,11 @@ pub const DAY: Day<Monkeys, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
+    },
+    examples: &[Example::new("main", include_str!("../../examples/day11.in.txt"))],
+    params: DayParams {
+        input: PARAMS,
+        example: PARAMS,
     },
-    example: include_str!("../../examples/day11.in.txt"),
 };
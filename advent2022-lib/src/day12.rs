@@ -3,7 +3,8 @@ use std::str::FromStr;
 
 use pathfinding::directed::fringe::fringe;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Height(usize);
@@ -15,11 +16,12 @@ impl Height {
     fn end() -> Self {
         Self('z' as usize - 'a' as usize)
     }
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            'S' => Self::start(),
-            'E' => Self::end(),
-            s => Self(s as usize - 'a' as usize),
+            'S' => Some(Self::start()),
+            'E' => Some(Self::end()),
+            s if s.is_ascii_lowercase() => Some(Self(s as usize - 'a' as usize)),
+            _ => None,
         }
     }
 }
@@ -39,23 +41,31 @@ impl FromStr for HeightMap {
         let mut start = None;
         let mut end = None;
         for (row_idx, line) in s.lines().enumerate() {
-            for (col_idx, c) in line.chars().enumerate() {
+            for (col_idx, (byte_idx, c)) in line.char_indices().enumerate() {
                 let pos = (row_idx + 1, col_idx + 1);
-                if c == 'S' {
-                    assert!(start.is_none());
-                    start = Some(pos)
+                let error = |message: String| {
+                    ParseError::Str(message).locate(s, &line[byte_idx..byte_idx + c.len_utf8()])
+                };
+                let marker = match c {
+                    'S' => Some(&mut start),
+                    'E' => Some(&mut end),
+                    _ => None,
+                };
+                if let Some(marker) = marker {
+                    if marker.is_some() {
+                        return Err(error(format!("duplicate {c:?} marker")));
+                    }
+                    *marker = Some(pos);
                 }
-                if c == 'E' {
-                    assert!(end.is_none());
-                    end = Some(pos)
-                }
-                map.insert(pos, Height::from_char(c));
+                let height =
+                    Height::from_char(c).ok_or_else(|| error(format!("invalid height {c:?}")))?;
+                map.insert(pos, height);
             }
         }
         Ok(HeightMap {
             map,
-            start: start.unwrap(),
-            end: end.unwrap(),
+            start: start.ok_or_else(|| ParseError::Str(String::from("missing start 'S'")))?,
+            end: end.ok_or_else(|| ParseError::Str(String::from("missing end 'E'")))?,
         })
     }
 }
@@ -118,7 +128,7 @@ fn pathfind<HS: FnMut(&Node) -> usize, FS: FnMut(&Node) -> bool>(
     fringe(&start, successors, heuristic, success)
 }
 
-pub fn part1(height_map: &HeightMap) -> PartOutput<usize> {
+pub fn part1(height_map: &HeightMap, _params: &()) -> SolveResult<PartOutput<usize>> {
     let start = Node {
         pos: height_map.start,
         height: Height::start(),
@@ -130,12 +140,17 @@ pub fn part1(height_map: &HeightMap) -> PartOutput<usize> {
         log::debug!("success {:?} <> {:?}", n.pos, height_map.end);
         n.pos == height_map.end
     };
-    let (path, steps) = pathfind(&height_map.map, start, success, heuristic, Dir::Up).unwrap();
+    let (path, steps) =
+        pathfind(&height_map.map, start, success, heuristic, Dir::Up).ok_or_else(|| {
+            SolveError::NoSolution(String::from(
+                "no path climbs from the start to the best signal",
+            ))
+        })?;
     log::debug!("path: {path:?}");
-    PartOutput { answer: steps }
+    Ok(PartOutput { answer: steps })
 }
 
-pub fn part2(height_map: &HeightMap) -> PartOutput<usize> {
+pub fn part2(height_map: &HeightMap, _params: &()) -> SolveResult<PartOutput<usize>> {
     let start = Node {
         pos: height_map.end,
         height: Height::end(),
@@ -151,12 +166,17 @@ pub fn part2(height_map: &HeightMap) -> PartOutput<usize> {
             .iter()
             .map(|end| n.pos.0.abs_diff(end.0) + n.pos.1.abs_diff(end.1))
             .min()
-            .unwrap()
+            .unwrap_or_default()
     };
     let success = |n: &Node| -> bool { n.height == Height::start() };
-    let (path, steps) = pathfind(&height_map.map, start, success, heuristic, Dir::Down).unwrap();
+    let (path, steps) = pathfind(&height_map.map, start, success, heuristic, Dir::Down)
+        .ok_or_else(|| {
+            SolveError::NoSolution(String::from(
+                "no square with elevation a can reach the best signal",
+            ))
+        })?;
     log::debug!("path: {path:?}");
-    PartOutput { answer: steps }
+    Ok(PartOutput { answer: steps })
 }
 
 pub const DAY: Day<HeightMap, usize> = Day {
@@ -169,6 +189,8 @@ pub const DAY: Day<HeightMap, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day12.in.txt"),
+    examples: &[Example::new("main", include_str!("../../examples/day12.in.txt"))],
+    params: NO_PARAMS,
 };
@@ -1,7 +1,8 @@
 use std::iter::Peekable;
 use std::str::FromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::NO_PARAMS;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
 
 #[derive(Debug, PartialEq)]
 pub enum Token {
@@ -37,7 +38,11 @@ impl FromStr for FlatPacket {
                         tokens.push(Token::Close);
                     },
                     '[' => {
-                        assert!(remainder.is_empty());
+                        if !remainder.is_empty() {
+                            return Err(ParseError::Str(String::from(
+                                "unexpected '[' after a number",
+                            )));
+                        }
                         tokens.push(Token::Open);
                     },
                     _ => remainder.push(c),
@@ -45,7 +50,9 @@ impl FromStr for FlatPacket {
                 Ok((tokens, remainder))
             },
         )?;
-        assert_eq!(folded.1.len(), 0);
+        if !folded.1.is_empty() {
+            return Err(ParseError::Str(String::from("unterminated number")));
+        }
         Ok(Self(folded.0))
     }
 }
@@ -60,22 +67,36 @@ impl TryFrom<FlatPacket> for Packet {
     type Error = ParseError;
 
     fn try_from(value: FlatPacket) -> Result<Self, Self::Error> {
-        fn parse_tokens(tokens: &mut Peekable<impl Iterator<Item = Token>>) -> Packet {
-            match tokens.next().unwrap() {
-                Token::Num(v) => {
-                    return Packet::Integer(v);
+        fn parse_tokens(tokens: &mut Peekable<impl Iterator<Item = Token>>) -> ParseResult<Packet> {
+            match tokens.next() {
+                Some(Token::Num(v)) => {
+                    return Ok(Packet::Integer(v));
+                },
+                Some(Token::Close) => {
+                    return Err(ParseError::Str(String::from("unbalanced ']'")));
                 },
-                Token::Close => unreachable!(),
-                Token::Open => {},
+                Some(Token::Open) => {},
+                None => return Err(ParseError::Str(String::from("empty packet"))),
             }
             let mut retval = Vec::new();
-            while let Token::Open | Token::Num(_) = tokens.peek().unwrap() {
-                retval.push(parse_tokens(tokens))
+            loop {
+                match tokens.peek() {
+                    Some(Token::Open | Token::Num(_)) => retval.push(parse_tokens(tokens)?),
+                    Some(Token::Close) => break,
+                    None => return Err(ParseError::Str(String::from("unbalanced '['"))),
+                }
             }
             let _ = tokens.next();
-            Packet::List(retval)
+            Ok(Packet::List(retval))
+        }
+        let mut tokens = value.0.into_iter().peekable();
+        let packet = parse_tokens(&mut tokens)?;
+        if tokens.next().is_some() {
+            return Err(ParseError::Str(String::from(
+                "unexpected tokens after the end of the packet",
+            )));
         }
-        Ok(parse_tokens(&mut value.0.into_iter().peekable()))
+        Ok(packet)
     }
 }
 
@@ -89,11 +110,21 @@ impl FromStr for PacketPairs {
         Ok(Self(
             s.split("\n\n")
                 .map(|lines| {
-                    let (left, right) = lines.split_once('\n').unwrap();
-                    Ok([
-                        FlatPacket::from_str(left)?.try_into()?,
-                        FlatPacket::from_str(right)?.try_into()?,
-                    ])
+                    let (left, right) = match lines.lines().collect::<Vec<_>>()[..] {
+                        [left, right] => (left, right),
+                        _ => {
+                            return Err(ParseError::Str(String::from(
+                                "expected a pair of packets on consecutive lines",
+                            ))
+                            .locate(s, lines))
+                        },
+                    };
+                    let packet = |line: &str| -> ParseResult<Packet> {
+                        FlatPacket::from_str(line)
+                            .and_then(Packet::try_from)
+                            .map_err(|e| e.locate(s, line))
+                    };
+                    Ok([packet(left)?, packet(right)?])
                 })
                 .collect::<Result<_, ParseError>>()?,
         ))
@@ -162,26 +193,34 @@ impl PartialEq for &Packet {
 
 impl Eq for &Packet {}
 
-pub fn part1(packet_pairs: &PacketPairs) -> PartOutput<usize> {
+pub fn part1(packet_pairs: &PacketPairs, _params: &()) -> SolveResult<PartOutput<usize>> {
     log::info!("packet_pairs={packet_pairs:?}");
-    let sum_indices = packet_pairs
+    let ordered = packet_pairs
         .0
         .iter()
         .enumerate()
-        .filter(|(idx, [left_packet, right_packet])| {
+        .map(|(idx, [left_packet, right_packet])| {
             let result = is_pair_ordered(left_packet, right_packet);
             log::trace!("Pair {}: {:?}, {:?}", idx + 1, left_packet, right_packet);
             log::debug!("Pair {}: {:?}", idx + 1, result);
-            result.unwrap()
+            result.ok_or_else(|| {
+                SolveError::Unsupported(format!(
+                    "pair {} contains two identical packets, so its order is undecided",
+                    idx + 1
+                ))
+            })
         })
-        .map(|(idx, _)| idx + 1)
-        .sum();
-    PartOutput {
-        answer: sum_indices,
-    }
+        .collect::<SolveResult<Vec<bool>>>()?;
+    Ok(PartOutput {
+        answer: ordered
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, ordered)| ordered.then_some(idx + 1))
+            .sum(),
+    })
 }
 
-pub fn part2(packet_pairs: &PacketPairs) -> PartOutput<usize> {
+pub fn part2(packet_pairs: &PacketPairs, _params: &()) -> SolveResult<PartOutput<usize>> {
     let dividers = [
         Packet::try_from(FlatPacket::from_str("[[2]]").unwrap()).unwrap(),
         Packet::try_from(FlatPacket::from_str("[[6]]").unwrap()).unwrap(),
@@ -198,9 +237,9 @@ pub fn part2(packet_pairs: &PacketPairs) -> PartOutput<usize> {
         .iter()
         .map(|div| packets.binary_search(&div).unwrap() + 1)
         .collect();
-    PartOutput {
+    Ok(PartOutput {
         answer: indices_base_1.iter().product(),
-    }
+    })
 }
 
 pub const DAY: Day<PacketPairs, usize> = Day {
@@ -213,16 +252,106 @@ pub const DAY: Day<PacketPairs, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
     },
-    example: include_str!("../../examples/day13.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day13.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
+    use proptest::prelude::*;
     use test_log::test;
 
     use super::*;
 
+    #[derive(Debug, Clone)]
+    enum Value {
+        Int(usize),
+        List(Vec<Value>),
+    }
+
+    impl Value {
+        fn render(&self) -> String {
+            match self {
+                Self::Int(v) => v.to_string(),
+                Self::List(values) => format!(
+                    "[{}]",
+                    values
+                        .iter()
+                        .map(Self::render)
+                        .collect::<Vec<_>>()
+                        .join(",")
+                ),
+            }
+        }
+
+        fn compare(&self, other: &Self) -> Ordering {
+            match (self, other) {
+                (Self::Int(left), Self::Int(right)) => left.cmp(right),
+                (Self::Int(_), Self::List(_)) => Self::List(vec![self.clone()]).compare(other),
+                (Self::List(_), Self::Int(_)) => self.compare(&Self::List(vec![other.clone()])),
+                (Self::List(left), Self::List(right)) => left
+                    .iter()
+                    .zip(right)
+                    .map(|(left, right)| left.compare(right))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| left.len().cmp(&right.len())),
+            }
+        }
+    }
+
+    /// A packet, which is always a list, with numbers of up to two digits nested a few deep
+    fn packet() -> impl Strategy<Value = Value> {
+        let value = (0..11usize)
+            .prop_map(Value::Int)
+            .prop_recursive(3, 16, 4, |inner| {
+                prop::collection::vec(inner, 0..4).prop_map(Value::List)
+            });
+        prop::collection::vec(value, 0..4).prop_map(Value::List)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force(pairs in prop::collection::vec((packet(), packet()), 1..8)) {
+            let input = pairs
+                .iter()
+                .map(|(left, right)| format!("{}\n{}", left.render(), right.render()))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let parsed = parse(&input).unwrap();
+
+            // Pairs of equal packets have no order
+            let expected1 = pairs
+                .iter()
+                .enumerate()
+                .map(|(idx, (left, right))| match left.compare(right) {
+                    Ordering::Less => Some(idx + 1),
+                    Ordering::Greater => Some(0),
+                    Ordering::Equal => None,
+                })
+                .sum::<Option<usize>>();
+            prop_assert_eq!(part1(&parsed, &()).ok().map(|output| output.answer), expected1);
+
+            // A packet equal to a divider could go either side of it
+            let dividers = [2, 6].map(|v| Value::List(vec![Value::List(vec![Value::Int(v)])]));
+            let packets: Vec<&Value> = pairs.iter().flat_map(|(left, right)| [left, right]).collect();
+            if packets.iter().any(|packet| dividers.iter().any(|divider| packet.compare(divider).is_eq())) {
+                return Ok(());
+            }
+            let before = |divider: &Value| {
+                packets.iter().filter(|packet| packet.compare(divider).is_lt()).count()
+            };
+            let expected2 = (before(&dividers[0]) + 1) * (before(&dividers[1]) + 2);
+            prop_assert_eq!(part2(&parsed, &()).unwrap().answer, expected2);
+        }
+    }
+
     #[test]
     fn test_parse() {
         let packet: Packet = FlatPacket::from_str("[[9,[10]]]")
@@ -254,7 +383,7 @@ mod tests {
                 .unwrap()
                 .try_into()
                 .unwrap(),
-            &FlatPacket::from_str("[[[[9],5]]]]")
+            &FlatPacket::from_str("[[[[9],5]]]")
                 .unwrap()
                 .try_into()
                 .unwrap(),
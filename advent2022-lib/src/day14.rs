@@ -1,7 +1,21 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::{Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::DayParams;
+use crate::parser::parse_lines;
+use crate::{
+    params, Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult,
+};
+
+params! {
+    pub struct Params {
+        /// The column the sand pours in from, at the top of the cave
+        pub source_x: usize,
+    }
+}
+
+/// The most positions a rock line may cover, far more than any cave the sand can fill in time
+const MAX_LINE_LEN: usize = 100_000;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Pos {
@@ -9,24 +23,31 @@ pub struct Pos {
     y: usize,
 }
 impl Pos {
-    fn to(&self, end: &Pos) -> Vec<Pos> {
+    /// Every position on the straight line to `end`
+    fn to(&self, end: &Pos) -> ParseResult<Vec<Pos>> {
+        if self.x != end.x && self.y != end.y {
+            return Err(ParseError::Str(format!(
+                "{self:?} and {end:?} cannot be connected by a straight line"
+            )));
+        }
+        // checked before anything is allocated, as a long enough line cannot be held
+        let len = (self.x.abs_diff(end.x) + self.y.abs_diff(end.y))
+            .checked_add(1)
+            .filter(|&len| len <= MAX_LINE_LEN)
+            .ok_or_else(|| {
+                ParseError::Str(format!(
+                    "{self:?} -> {end:?} is longer than {MAX_LINE_LEN} positions"
+                ))
+            })?;
+        let mut line = Vec::with_capacity(len);
         if self.x == end.x {
-            let range = if self.y <= end.y {
-                self.y..=end.y
-            } else {
-                end.y..=self.y
-            };
-            range.into_iter().map(|y| Pos { x: self.x, y }).collect()
-        } else if self.y == end.y {
-            let range = if self.x <= end.x {
-                self.x..=end.x
-            } else {
-                end.x..=self.x
-            };
-            range.into_iter().map(|x| Pos { x, y: self.y }).collect()
+            let range = self.y.min(end.y)..=self.y.max(end.y);
+            line.extend(range.map(|y| Pos { x: self.x, y }));
         } else {
-            panic!("{self:?} and {end:?} cannot be connected")
+            let range = self.x.min(end.x)..=self.x.max(end.x);
+            line.extend(range.map(|x| Pos { x, y: self.y }));
         }
+        Ok(line)
     }
 }
 
@@ -38,12 +59,36 @@ impl FromStr for Pos {
             .split_once(',')
             .ok_or(ParseError::Str(format!("invalid pos {s}")))?;
         Ok(Self {
-            x: x.parse()?,
-            y: y.parse()?,
+            x: x.parse().map_err(|e| ParseError::from(e).locate(s, x))?,
+            y: y.parse().map_err(|e| ParseError::from(e).locate(s, y))?,
         })
     }
 }
 
+/// Every rock position traced by a path
+#[derive(Debug)]
+pub struct Path(Vec<Pos>);
+
+impl FromStr for Path {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rocks = Vec::new();
+        let mut prev: Option<Pos> = None;
+        for pos_str in s.split(" -> ") {
+            let pos: Pos = pos_str
+                .parse()
+                .map_err(|e: ParseError| e.locate(s, pos_str))?;
+            match prev {
+                None => rocks.push(pos),
+                Some(prev) => rocks.extend(prev.to(&pos).map_err(|e| e.locate(s, pos_str))?),
+            }
+            prev = Some(pos);
+        }
+        Ok(Self(rocks))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Fill {
     // Air,
@@ -61,34 +106,46 @@ impl FromStr for Cave {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let paths: Vec<Vec<Pos>> = s
-            .lines()
-            .map(|line| {
-                line.split(" -> ")
-                    .map(FromStr::from_str)
-                    .collect::<Result<_, _>>()
-            })
-            .collect::<Result<_, _>>()?;
-        let mut map = HashMap::new();
-        for path in paths {
-            for window in path.windows(2) {
-                let [begin, end] = match window {
-                    [] => unreachable!(),
-                    [_] => unreachable!(),
-                    [begin, end] => [begin, end],
-                    [_, _, ..] => unreachable!(),
-                };
-                for pos in begin.to(end) {
-                    let _ = map.insert(pos, Fill::Rock);
-                }
-            }
-        }
-        let abyss = map.keys().map(|pos| pos.y).max().unwrap() + 2;
+        let paths: Vec<Path> = parse_lines(s)?;
+        let map: HashMap<_, _> = paths
+            .into_iter()
+            .flat_map(|path| path.0)
+            .map(|pos| (pos, Fill::Rock))
+            .collect();
+        let abyss = map
+            .keys()
+            .map(|pos| pos.y)
+            .max()
+            .ok_or_else(|| ParseError::Str(String::from("no rock paths")))?
+            .checked_add(2)
+            .ok_or_else(|| ParseError::Str(String::from("the rocks are too deep")))?;
         Ok(Self { map, abyss })
     }
 }
 
 impl Cave {
+    /// Sand spreads at most one column per row, so a deep enough cave lets it fall past x=0
+    fn check_depth(&self, source_x: usize) -> SolveResult<()> {
+        if self.abyss > source_x || source_x.checked_add(self.abyss + 1).is_none() {
+            return Err(SolveError::Unsupported(format!(
+                "sand falling {} rows from x={source_x} could pass the edge of the cave",
+                self.abyss
+            )));
+        }
+        Ok(())
+    }
+
+    /// Sand cannot pour in at all when a rock covers its source
+    fn check_source(&self, source: &Pos) -> SolveResult<()> {
+        if self.map.contains_key(source) {
+            return Err(SolveError::Unsupported(format!(
+                "a rock covers the sand source at {},{}",
+                source.x, source.y
+            )));
+        }
+        Ok(())
+    }
+
     fn next_sand(&self, pos: &Pos) -> Option<Pos> {
         let next_pos = Pos {
             x: pos.x,
@@ -118,8 +175,8 @@ impl Cave {
         }
     }
 
-    fn drop_sand(&self) -> Option<Pos> {
-        let mut drop_sand = Pos { x: 500, y: 0 };
+    fn drop_sand(&self, source: Pos) -> Option<Pos> {
+        let mut drop_sand = source;
         loop {
             if let Some(next_sand) = self.next_sand(&drop_sand) {
                 if next_sand.y >= self.abyss {
@@ -132,11 +189,15 @@ impl Cave {
         }
     }
 
-    fn add_sand(&mut self) -> Option<Pos> {
-        let drop_sand = self.drop_sand();
+    /// Where a unit of sand from the source comes to rest, or `None` if it falls into the abyss
+    /// or the source is already blocked
+    fn add_sand(&mut self, source: Pos) -> Option<Pos> {
+        if self.map.contains_key(&source) {
+            return None;
+        }
+        let drop_sand = self.drop_sand(source);
         if let Some(sand) = drop_sand {
-            let existing = self.map.insert(sand, Fill::Sand);
-            assert!(existing.is_none())
+            self.map.insert(sand, Fill::Sand);
         }
         drop_sand
     }
@@ -148,41 +209,42 @@ pub fn parse(input: &str) -> ParseResult<Cave> {
     Ok(cave)
 }
 
-pub fn part1(cave: &Cave) -> PartOutput<usize> {
+pub fn part1(cave: &Cave, params: &Params) -> SolveResult<PartOutput<usize>> {
+    cave.check_depth(params.source_x)?;
+    let source = Pos {
+        x: params.source_x,
+        y: 0,
+    };
+    cave.check_source(&source)?;
     let mut cave = cave.clone();
     let mut counter = 0;
-    while cave.add_sand().is_some() {
+    while cave.add_sand(source).is_some() {
         counter += 1;
     }
-    PartOutput { answer: counter }
+    Ok(PartOutput { answer: counter })
 }
 
-pub fn part2(cave: &Cave) -> PartOutput<usize> {
-    let mut cave = cave.clone();
-    let begin = Pos {
-        x: 500 - cave.abyss,
-        y: cave.abyss,
-    };
-    let end = Pos {
-        x: 500 + cave.abyss,
-        y: cave.abyss,
+pub fn part2(cave: &Cave, params: &Params) -> SolveResult<PartOutput<usize>> {
+    cave.check_depth(params.source_x)?;
+    let source = Pos {
+        x: params.source_x,
+        y: 0,
     };
-    for pos in begin.to(&end) {
-        let _ = cave.map.insert(pos, Fill::Rock);
+    cave.check_source(&source)?;
+    let mut cave = cave.clone();
+    for x in source.x - cave.abyss..=source.x + cave.abyss {
+        let _ = cave.map.insert(Pos { x, y: cave.abyss }, Fill::Rock);
     }
-    let mut counter = 1;
-    loop {
-        if let Some(pos) = cave.add_sand() {
-            if pos.x == 500 && pos.y == 0 {
-                break;
-            }
-        }
+    let mut counter = 0;
+    while cave.add_sand(source).is_some() {
         counter += 1;
     }
-    PartOutput { answer: counter }
+    Ok(PartOutput { answer: counter })
 }
 
-pub const DAY: Day<Cave, usize> = Day {
+const PARAMS: Params = Params { source_x: 500 };
+
+pub const DAY: Day<Cave, usize, Params> = Day {
     title: "Regolith Reservoir",
     display: (
         "{answer} units of sand come to rest before sand starts flowing into the abyss below",
@@ -192,6 +254,43 @@ pub const DAY: Day<Cave, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
+    },
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day14.in.txt"),
+    )],
+    params: DayParams {
+        input: PARAMS,
+        example: PARAMS,
     },
-    example: include_str!("../../examples/day14.in.txt"),
 };
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn test_source_blocked_by_sand() {
+        // the first unit of sand rests on the rocks right below the source, blocking it
+        let cave = parse("499,1 -> 501,1\n").unwrap();
+        assert_eq!(part1(&cave, &PARAMS).unwrap().answer, 1);
+        assert_eq!(part2(&cave, &PARAMS).unwrap().answer, 1);
+    }
+
+    #[test]
+    fn test_line_too_long() {
+        let error = parse(
+            "498,4 -> 498,6
+0,0 -> 0,100000000000
+",
+        )
+        .unwrap_err();
+        let location = error.location().unwrap();
+        assert_eq!(location.line, 2);
+        assert_eq!(&location.text[location.columns.clone()], "0,100000000000");
+        assert!(parse(&format!("0,0 -> 0,{}\n", MAX_LINE_LEN - 1)).is_ok());
+    }
+}
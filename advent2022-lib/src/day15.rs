@@ -2,7 +2,21 @@ use std::collections::HashSet;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
-use crate::{regex_once, Day, DayCalc, ParseError, ParseResult, PartOutput};
+use crate::params::DayParams;
+use crate::parser::parse_lines;
+use crate::{
+    params, regex_once, Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError,
+    SolveResult,
+};
+
+params! {
+    pub struct Params {
+        /// The row to count the positions that cannot contain a beacon in
+        pub row: isize,
+        /// The largest x and y the distress beacon can be at
+        pub bound: usize,
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Pos {
@@ -15,17 +29,24 @@ impl FromStr for Pos {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let re = regex_once!(r#"x=(?P<x>\-?\d+), y=(?P<y>\-?\d+)"#);
-        let captures = re.captures(s).unwrap();
+        let captures = re
+            .captures(s)
+            .ok_or_else(|| ParseError::Str(format!("expected 'x=<x>, y=<y>', found {s:?}")))?;
+        let x = captures.name("x").unwrap().as_str();
+        let y = captures.name("y").unwrap().as_str();
         Ok(Self {
-            x: captures.name("x").unwrap().as_str().parse()?,
-            y: captures.name("y").unwrap().as_str().parse()?,
+            x: x.parse().map_err(|e| ParseError::from(e).locate(s, x))?,
+            y: y.parse().map_err(|e| ParseError::from(e).locate(s, y))?,
         })
     }
 }
 
 impl Pos {
-    const fn distance(&self, other: &Pos) -> usize {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    /// The Manhattan distance, unless it is too far to count
+    fn distance(&self, other: &Pos) -> Option<usize> {
+        self.x
+            .abs_diff(other.x)
+            .checked_add(self.y.abs_diff(other.y))
     }
 }
 
@@ -40,27 +61,38 @@ impl FromStr for Sensor {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let re = regex_once!(r#"Sensor at (?P<pos>.+): closest beacon is at (?P<beacon>.+)"#);
-        let captures = re.captures(s).unwrap();
+        let captures = re.captures(s).ok_or_else(|| {
+            ParseError::Str(String::from(
+                "expected 'Sensor at <pos>: closest beacon is at <pos>'",
+            ))
+        })?;
+        let pos = captures.name("pos").unwrap().as_str();
+        let beacon = captures.name("beacon").unwrap().as_str();
         Ok(Self {
-            pos: captures.name("pos").unwrap().as_str().parse()?,
-            beacon: captures.name("beacon").unwrap().as_str().parse()?,
+            pos: pos.parse().map_err(|e: ParseError| e.locate(s, pos))?,
+            beacon: beacon
+                .parse()
+                .map_err(|e: ParseError| e.locate(s, beacon))?,
         })
     }
 }
 
 impl Sensor {
-    fn y_range(&self, y: isize) -> Option<RangeInclusive<isize>> {
-        let distance = self.pos.distance(&self.beacon);
-        let distance_y = self.pos.y.abs_diff(y);
-        if distance_y > distance {
-            None
-        } else {
-            let remainder_y = distance.checked_sub(distance_y).unwrap();
-            Some(
-                self.pos.x.checked_sub_unsigned(remainder_y).unwrap()
-                    ..=self.pos.x.checked_add_unsigned(remainder_y).unwrap(),
-            )
-        }
+    /// The positions the sensor covers in row `y`, if any
+    fn y_range(&self, y: isize) -> SolveResult<Option<RangeInclusive<isize>>> {
+        let too_far = || {
+            SolveError::Unsupported(format!(
+                "the sensor at x={}, y={} covers positions too far away",
+                self.pos.x, self.pos.y
+            ))
+        };
+        let distance = self.pos.distance(&self.beacon).ok_or_else(too_far)?;
+        let Some(remainder_y) = distance.checked_sub(self.pos.y.abs_diff(y)) else {
+            return Ok(None);
+        };
+        let start = self.pos.x.checked_sub_unsigned(remainder_y);
+        let end = self.pos.x.checked_add_unsigned(remainder_y);
+        Ok(Some(start.ok_or_else(too_far)?..=end.ok_or_else(too_far)?))
     }
 }
 
@@ -71,9 +103,7 @@ impl FromStr for Sensors {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines().map(FromStr::from_str).collect::<Result<_, _>>()?,
-        ))
+        Ok(Self(parse_lines(s)?))
     }
 }
 
@@ -87,11 +117,14 @@ fn coalesce_ranges(ranges: &[RangeInclusive<isize>]) -> Vec<RangeInclusive<isize
 
     let mut retval = Vec::new();
 
-    let last = ranges.pop().unwrap();
+    let last = match ranges.pop() {
+        Some(last) => last,
+        None => return retval,
+    };
     let mut curr_end = *last.end();
     let mut curr_start = *last.start();
     while let Some(last) = ranges.pop() {
-        if *last.end() + 1 >= curr_start {
+        if last.end().saturating_add(1) >= curr_start {
             // overlap
             curr_start = std::cmp::min(*last.start(), curr_start);
         } else {
@@ -105,72 +138,80 @@ fn coalesce_ranges(ranges: &[RangeInclusive<isize>]) -> Vec<RangeInclusive<isize
     retval
 }
 
-pub fn part1(sensors: &Sensors) -> PartOutput<usize> {
+pub fn part1(sensors: &Sensors, params: &Params) -> SolveResult<PartOutput<usize>> {
     log::info!("sensors={sensors:?}");
-    let y_row: isize = if sensors.0.len() < 20 { 10 } else { 2_000_000 };
+    let y_row = params.row;
     let ranges: Vec<RangeInclusive<_>> = sensors
         .0
         .iter()
-        .filter_map(|sensor| sensor.y_range(y_row))
-        .collect();
+        .filter_map(|sensor| sensor.y_range(y_row).transpose())
+        .collect::<SolveResult<_>>()?;
     let non_overlapping_ranges = coalesce_ranges(&ranges);
-    let sensor_and_beacon: HashSet<_> = sensors
+    // A sensor's own position cannot hold a beacon either, so only beacons are removed
+    let beacons: HashSet<_> = sensors
         .0
         .iter()
-        .flat_map(|sensor| {
-            let mut retval = Vec::new();
-            if sensor.beacon.y == y_row {
-                retval.push(sensor.beacon.x)
-            }
-            if sensor.pos.y == y_row {
-                retval.push(sensor.pos.x);
-            }
-            retval
-        })
+        .filter(|sensor| sensor.beacon.y == y_row)
+        .map(|sensor| sensor.beacon.x)
         .collect();
-    let positions: usize = non_overlapping_ranges
+    let positions = non_overlapping_ranges
         .into_iter()
-        .map(|range| {
-            let to_remove = sensor_and_beacon
-                .iter()
-                .filter(|p| range.contains(p))
-                .count();
-            usize::try_from(range.end() - range.start() + 1).unwrap() - to_remove
+        .try_fold(0usize, |positions, range| {
+            let to_remove = beacons.iter().filter(|p| range.contains(p)).count();
+            let len = range.end().abs_diff(*range.start()).checked_add(1)?;
+            positions.checked_add(len - to_remove)
         })
-        .sum();
+        .ok_or_else(|| {
+            SolveError::Unsupported(format!("too many positions in row {y_row} to count"))
+        })?;
     log::debug!("positions={positions:?}");
-    PartOutput { answer: positions }
+    Ok(PartOutput { answer: positions })
 }
 
-pub fn part2(sensors: &Sensors) -> PartOutput<usize> {
-    let mut beacon_x: Option<usize> = None;
-    let mut beacon_y: Option<usize> = None;
-    let y_range: usize = if sensors.0.len() < 20 { 20 } else { 4_000_000 };
-    for y in 0..=y_range {
-        let ranges: Vec<RangeInclusive<_>> = sensors
-            .0
-            .iter()
-            .filter_map(|sensor| sensor.y_range(y.try_into().unwrap()))
-            .collect();
-        if ranges.is_empty() {
-            continue;
+pub fn part2(sensors: &Sensors, params: &Params) -> SolveResult<PartOutput<usize>> {
+    let bound = isize::try_from(params.bound)
+        .map_err(|_| SolveError::Unsupported(format!("the bound {} is too large", params.bound)))?;
+    let mut ranges = Vec::new();
+    for y in 0..=bound {
+        ranges.clear();
+        for sensor in &sensors.0 {
+            // Only the coverage within the bounds matters, as the beacon cannot be outside them
+            if let Some(range) = sensor.y_range(y)? {
+                let range = std::cmp::max(*range.start(), 0)..=std::cmp::min(*range.end(), bound);
+                if !range.is_empty() {
+                    ranges.push(range);
+                }
+            }
         }
-        let non_overlapping_ranges = coalesce_ranges(&ranges);
-        if non_overlapping_ranges.len() > 1 {
-            log::debug!("{non_overlapping_ranges:?}");
-            beacon_x = Some(
-                usize::try_from(*non_overlapping_ranges.first().unwrap().start() - 1).unwrap(),
-            );
-            beacon_y = Some(y);
-            break;
+        // The coalesced ranges neither overlap nor touch, so the first uncovered position is
+        // before the leftmost one or right after it
+        let mut beacon_x = Some(0);
+        for range in coalesce_ranges(&ranges).iter().rev() {
+            match beacon_x {
+                Some(x) if *range.start() <= x => beacon_x = range.end().checked_add(1),
+                _ => break,
+            }
+        }
+        if let Some(beacon_x) = beacon_x.filter(|&x| x <= bound) {
+            log::debug!("{ranges:?}");
+            let frequency = beacon_x
+                .checked_mul(4_000_000)
+                .and_then(|frequency| frequency.checked_add(y))
+                .and_then(|frequency| usize::try_from(frequency).ok())
+                .ok_or_else(|| {
+                    SolveError::Unsupported(format!(
+                        "the tuning frequency of x={beacon_x}, y={y} is too large"
+                    ))
+                })?;
+            return Ok(PartOutput { answer: frequency });
         }
     }
-    PartOutput {
-        answer: beacon_x.unwrap() * 4_000_000 + beacon_y.unwrap(),
-    }
+    Err(SolveError::NoSolution(format!(
+        "every position with x and y between 0 and {bound} is covered by a sensor"
+    )))
 }
 
-pub const DAY: Day<Sensors, usize> = Day {
+pub const DAY: Day<Sensors, usize, Params> = Day {
     title: "Beacon Exclusion Zone",
     display: (
         "{answer} positions cannot contain a beacon",
@@ -180,27 +221,163 @@ pub const DAY: Day<Sensors, usize> = Day {
         parse,
         part1,
         part2,
+        explain: None,
+    },
+    examples: &[
+        Example::new("main", include_str!("../../examples/day15.in.txt")),
+        // The row with a sensor on it, whose position cannot hold a beacon
+        Example::new("row11", include_str!("../../examples/day15-row11.in.txt"))
+            .with_params(&[("row", "11")]),
+    ],
+    params: DayParams {
+        input: Params {
+            row: 2_000_000,
+            bound: 4_000_000,
+        },
+        example: Params { row: 10, bound: 20 },
     },
-    example: include_str!("../../examples/day15.in.txt"),
 };
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use test_log::test;
 
     use super::*;
 
+    const BOUND: isize = 12;
+
+    /// A sensor's position and its closest beacon's
+    type Reading = ((isize, isize), (isize, isize));
+
+    fn input(sensors: &[Reading]) -> String {
+        sensors
+            .iter()
+            .map(|((sx, sy), (bx, by))| {
+                format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn covers(((sx, sy), (bx, by)): Reading, (x, y): (isize, isize)) -> bool {
+        (sx - x).abs() + (sy - y).abs() <= (sx - bx).abs() + (sy - by).abs()
+    }
+
+    fn coordinate() -> impl Strategy<Value = isize> {
+        -BOUND..=2 * BOUND
+    }
+
+    /// Sensors around a distress beacon whose coverage reaches up to, but not including, it
+    fn hidden_beacon() -> impl Strategy<Value = ((isize, isize), Vec<Reading>)> {
+        let sensor = (coordinate(), coordinate(), any::<bool>());
+        ((0..=BOUND, 0..=BOUND), prop::collection::vec(sensor, 4..12)).prop_map(
+            |((x, y), sensors)| {
+                let sensors = sensors
+                    .into_iter()
+                    .filter(|&(sx, sy, _)| (sx, sy) != (x, y))
+                    .map(|(sx, sy, horizontal)| {
+                        let radius = (sx - x).abs() + (sy - y).abs() - 1;
+                        let beacon = if horizontal {
+                            (sx + radius, sy)
+                        } else {
+                            (sx, sy - radius)
+                        };
+                        ((sx, sy), beacon)
+                    })
+                    .collect();
+                ((x, y), sensors)
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_coalesce_ranges_matches_brute_force(
+            ranges in prop::collection::vec((0..20isize, 0..4isize), 0..8)
+        ) {
+            let ranges: Vec<_> = ranges.iter().map(|&(start, len)| start..=start + len).collect();
+            let mut coalesced = coalesce_ranges(&ranges);
+            coalesced.sort_unstable_by_key(|range| *range.start());
+            for x in -1..25 {
+                prop_assert_eq!(
+                    coalesced.iter().any(|range| range.contains(&x)),
+                    ranges.iter().any(|range| range.contains(&x))
+                );
+            }
+            // Touching ranges are merged too
+            for pair in coalesced.windows(2) {
+                prop_assert!(*pair[0].end() + 1 < *pair[1].start());
+            }
+        }
+
+        #[test]
+        fn test_part1_matches_brute_force(
+            sensors in prop::collection::vec(((coordinate(), coordinate()), (coordinate(), coordinate())), 1..8),
+            row in coordinate(),
+        ) {
+            // Far enough out for the largest sensor range of coordinates this far apart
+            let (min_x, max_x) = (-7 * BOUND, 8 * BOUND);
+            let expected = (min_x..=max_x)
+                .filter(|&x| sensors.iter().any(|&sensor| covers(sensor, (x, row))))
+                .filter(|&x| !sensors.iter().any(|&(_, beacon)| beacon == (x, row)))
+                .count();
+            let parsed = parse(&input(&sensors)).unwrap();
+            let params = Params { row, bound: BOUND as usize };
+            prop_assert_eq!(part1(&parsed, &params).unwrap().answer, expected);
+        }
+
+        #[test]
+        fn test_part2_matches_brute_force((beacon, sensors) in hidden_beacon()) {
+            let uncovered: Vec<_> = (0..=BOUND)
+                .flat_map(|y| (0..=BOUND).map(move |x| (x, y)))
+                .filter(|&pos| !sensors.iter().any(|&sensor| covers(sensor, pos)))
+                .collect();
+            // The puzzle promises a single position the distress beacon could be at
+            prop_assume!(uncovered == [beacon]);
+            let parsed = parse(&input(&sensors)).unwrap();
+            let params = Params { row: 0, bound: BOUND as usize };
+            let (x, y) = beacon;
+            prop_assert_eq!(
+                part2(&parsed, &params).unwrap().answer,
+                x as usize * 4_000_000 + y as usize
+            );
+        }
+    }
+
     #[test]
     fn test_sensor() {
         let sensor = Sensor {
             pos: Pos { x: 10, y: 10 },
             beacon: Pos { x: 20, y: 10 },
         };
-        assert_eq!(sensor.y_range(20).unwrap().into_iter().count(), 1);
-        assert_eq!(sensor.y_range(0).unwrap().into_iter().count(), 1);
-        assert_eq!(sensor.y_range(1).unwrap().into_iter().count(), 3);
-        assert_eq!(sensor.y_range(2).unwrap().into_iter().count(), 5);
-        assert!(sensor.y_range(21).is_none());
+        assert_eq!(sensor.y_range(20).unwrap().unwrap().into_iter().count(), 1);
+        assert_eq!(sensor.y_range(0).unwrap().unwrap().into_iter().count(), 1);
+        assert_eq!(sensor.y_range(1).unwrap().unwrap().into_iter().count(), 3);
+        assert_eq!(sensor.y_range(2).unwrap().unwrap().into_iter().count(), 5);
+        assert!(sensor.y_range(21).unwrap().is_none());
+
+        // coverage reaching past the edge of the coordinate space cannot be counted
+        let far = Sensor {
+            pos: Pos {
+                x: isize::MAX,
+                y: 0,
+            },
+            beacon: Pos {
+                x: isize::MAX,
+                y: 10,
+            },
+        };
+        assert!(far.y_range(0).is_err());
+        assert!(far.y_range(isize::MIN).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_part1_row_with_sensor() {
+        // The sensor at x=0 can cover x=-2..=2 on its own row, and holds no beacon itself
+        let sensors = parse("Sensor at x=0, y=0: closest beacon is at x=2, y=0").unwrap();
+        let params = Params { row: 0, bound: 0 };
+        assert_eq!(part1(&sensors, &params).unwrap().answer, 4);
     }
 
     #[test]
@@ -1,10 +1,25 @@
-use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 use std::str::FromStr;
 
-use crate::{regex_once, Day, DayCalc, ParseError, ParseResult, PartOutput};
+use pathfinding::directed::bfs::bfs;
+use pathfinding::directed::dijkstra::dijkstra_all;
+
+use crate::params::NO_PARAMS;
+use crate::parser::parse_lines;
+use crate::{
+    regex_once, Day, DayCalc, Example, Explanation, ParseError, ParseResult, Part, PartOutput,
+    SolveError, SolveResult,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct ValveId([char; 2]);
+pub struct ValveId([char; 2]);
+
+impl Display for ValveId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}{}", self.0[0], self.0[1])
+    }
+}
 
 impl FromStr for ValveId {
     type Err = ParseError;
@@ -31,16 +46,30 @@ impl FromStr for ValveEntry {
         let re = regex_once!(
             r#"Valve (?P<identifier>\w+) has flow rate=(?P<rate>\d+); tunnels? leads? to valves? (?P<connections>.+)"#
         );
-        let captures = re.captures(s).unwrap();
+        let captures = re.captures(s).ok_or_else(|| {
+            ParseError::Str(String::from(
+                "expected 'Valve <id> has flow rate=<rate>; tunnels lead to valves <ids>'",
+            ))
+        })?;
+        let identifier = captures.name("identifier").unwrap().as_str();
+        let rate = captures.name("rate").unwrap().as_str();
         Ok(Self {
-            identifier: captures.name("identifier").unwrap().as_str().parse()?,
-            rate: captures.name("rate").unwrap().as_str().parse()?,
+            identifier: identifier
+                .parse()
+                .map_err(|e: ParseError| e.locate(s, identifier))?,
+            rate: rate
+                .parse()
+                .map_err(|e| ParseError::from(e).locate(s, rate))?,
             connections: captures
                 .name("connections")
                 .unwrap()
                 .as_str()
                 .split(", ")
-                .map(FromStr::from_str)
+                .map(|connection| {
+                    connection
+                        .parse()
+                        .map_err(|e: ParseError| e.locate(s, connection))
+                })
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -53,9 +82,33 @@ impl FromStr for ValveEntries {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines().map(FromStr::from_str).collect::<Result<_, _>>()?,
-        ))
+        let entries: Vec<ValveEntry> = parse_lines(s)?;
+        let mut identifiers: HashSet<&ValveId> = HashSet::new();
+        for (line, entry) in s.lines().zip(&entries) {
+            if !identifiers.insert(&entry.identifier) {
+                return Err(ParseError::Str(format!(
+                    "valve {} is described more than once",
+                    entry.identifier
+                ))
+                .locate(s, line));
+            }
+        }
+        if !identifiers.contains(&START) {
+            return Err(ParseError::Str(String::from("missing starting valve AA")));
+        }
+        for (line, entry) in s.lines().zip(&entries) {
+            if let Some(unknown) = entry
+                .connections
+                .iter()
+                .find(|connection| !identifiers.contains(connection))
+            {
+                return Err(
+                    ParseError::Str(format!("tunnel leads to unknown valve {unknown:?}"))
+                        .locate(s, line),
+                );
+            }
+        }
+        Ok(Self(entries))
     }
 }
 
@@ -63,207 +116,454 @@ pub fn parse(input: &str) -> ParseResult<ValveEntries> {
     input.parse()
 }
 
-#[derive(Clone, PartialEq, Eq)]
-pub struct Valve {
-    identifier: ValveId,
-    rate: usize,
-}
+/// The time before the volcano erupts
+const MINUTES: usize = 30;
+/// The time it takes to teach an elephant to help
+const ELEPHANT_TRAINING: usize = 4;
+/// Each set of valves is a bitmask indexing a table, so only so many can have flow rates
+const MAX_VALVES: usize = 20;
+const START: ValveId = ValveId(['A', 'A']);
 
-impl Ord for Valve {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.rate.cmp(&other.rate).reverse()
-    }
-}
+/// Where a route is, the minutes remaining and the bitmask of valves it has yet to open
+type RouteState = (Option<usize>, usize, usize);
 
-impl PartialOrd for Valve {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
-    }
+/// The valves worth opening, with how many minutes it takes to walk between them
+#[derive(Debug)]
+struct Network {
+    /// Each valve with a non-zero flow rate, and its rate
+    valves: Vec<(ValveId, usize)>,
+    /// Minutes to walk from the start to each valve, if it can be reached
+    from_start: Vec<Option<usize>>,
+    /// Minutes to walk from each valve to each other valve, if it can be reached
+    distances: Vec<Vec<Option<usize>>>,
 }
 
-struct ValveRate(HashMap<ValveId, Valve>);
+impl Network {
+    fn new(entries: &ValveEntries) -> SolveResult<Self> {
+        let valves: Vec<(ValveId, usize)> = entries
+            .0
+            .iter()
+            .filter(|v| v.rate > 0)
+            .map(|v| (v.identifier.clone(), v.rate))
+            .collect();
+        if valves.len() > MAX_VALVES {
+            return Err(SolveError::Unsupported(format!(
+                "{} valves have flow rates, but at most {MAX_VALVES} can be searched",
+                valves.len()
+            )));
+        }
+        // every valve open for the whole time bounds all the sums taken while searching
+        valves
+            .iter()
+            .try_fold(0usize, |total, (_, rate)| total.checked_add(*rate))
+            .and_then(|total| total.checked_mul(MINUTES))
+            .ok_or_else(|| SolveError::Unsupported(String::from("the flow rates are too high")))?;
+        let adjacency: HashMap<&ValveId, &[ValveId]> = entries
+            .0
+            .iter()
+            .map(|v| (&v.identifier, v.connections.as_slice()))
+            .collect();
+        let distances_from = |from: &ValveId| -> Vec<Option<usize>> {
+            let reached = dijkstra_all(from, |valve| {
+                adjacency[valve].iter().map(|next| (next.clone(), 1))
+            });
+            valves
+                .iter()
+                .map(|(to, _)| {
+                    if to == from {
+                        Some(0)
+                    } else {
+                        reached.get(to).map(|(_, minutes)| *minutes)
+                    }
+                })
+                .collect()
+        };
+        Ok(Self {
+            from_start: distances_from(&START),
+            distances: valves
+                .iter()
+                .map(|(from, _)| distances_from(from))
+                .collect(),
+            valves,
+        })
+    }
 
-#[derive(Clone, PartialEq, Eq)]
-struct State {
-    pressure_released: usize,
-    total_flow_rate: usize,
-    eventual_pressure_released: usize,
-    valves_open: HashSet<ValveId>,
-    valves_closed: BTreeSet<Valve>,
-    pos: ValveId,
-}
+    /// Minutes to walk to valve `to` from valve `from`, or from the start if `None`
+    fn distance(&self, from: Option<usize>, to: usize) -> Option<usize> {
+        match from {
+            Some(from) => self.distances[from][to],
+            None => self.from_start[to],
+        }
+    }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.eventual_pressure_released
-            .cmp(&other.eventual_pressure_released)
+    /// The most pressure released in `minutes` by opening exactly each set of valves in the best
+    /// order, indexed by the set's bitmask, or 0 if there is no time to open them all
+    fn best_by_valves(&self, minutes: usize) -> Vec<usize> {
+        let mut best = vec![0; 1 << self.valves.len()];
+        self.search(None, minutes, 0, 0, &mut best);
+        best
     }
-}
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
+    /// Try walking to and opening each closed valve next, recording the pressure every set of
+    /// opened valves releases
+    fn search(
+        &self,
+        pos: Option<usize>,
+        minutes_remaining: usize,
+        opened: usize,
+        released: usize,
+        best: &mut [usize],
+    ) {
+        best[opened] = best[opened].max(released);
+        for (next, (_, rate)) in self.valves.iter().enumerate() {
+            if opened & (1 << next) != 0 {
+                continue;
+            }
+            // opening a valve takes a minute, and it must then have time to release pressure
+            let remaining = self
+                .distance(pos, next)
+                .and_then(|distance| minutes_remaining.checked_sub(distance + 1))
+                .filter(|&remaining| remaining > 0);
+            if let Some(remaining) = remaining {
+                self.search(
+                    Some(next),
+                    remaining,
+                    opened | (1 << next),
+                    released + remaining * rate,
+                    best,
+                );
+            }
+        }
     }
-}
 
-impl State {
-    fn init(rates: &ValveRate) -> Self {
-        let valves_closed: BTreeSet<Valve> =
-            rates.0.values().filter(|v| v.rate > 0).cloned().collect();
-        Self {
-            pressure_released: 0,
-            total_flow_rate: 0,
-            eventual_pressure_released: 0,
-            valves_open: HashSet::new(),
-            valves_closed,
-            pos: ValveId(['A', 'A']),
+    /// The best order to open every valve in `closed` from the start, and the pressure that
+    /// releases, if there is time to open them all
+    fn route(&self, minutes_remaining: usize, closed: usize) -> Option<(usize, Vec<usize>)> {
+        let mut choices = HashMap::new();
+        let released = self.best_route(None, minutes_remaining, closed, &mut choices)?;
+        let mut order = Vec::new();
+        let mut state = (None, minutes_remaining, closed);
+        while state.2 != 0 {
+            let (_, next, remaining) = choices[&state]?;
+            order.push(next);
+            state = (Some(next), remaining, state.2 ^ (1 << next));
         }
+        Some((released, order))
     }
 
-    fn noop(self, minutes_remaining: usize) -> Self {
-        let pressure_released = self.pressure_released + self.total_flow_rate;
-        let total_flow_rate = self.total_flow_rate;
-        Self {
-            pressure_released,
-            total_flow_rate: total_flow_rate,
-            eventual_pressure_released: pressure_released + minutes_remaining * total_flow_rate,
-            valves_open: self.valves_open,
-            valves_closed: self.valves_closed,
-            pos: self.pos,
+    /// The most pressure released by opening every valve in `closed`, if there is time to open
+    /// them all, remembering the best valve to open next, and the minutes remaining once it is
+    /// open, for each position, time and set of closed valves
+    fn best_route(
+        &self,
+        pos: Option<usize>,
+        minutes_remaining: usize,
+        closed: usize,
+        choices: &mut HashMap<RouteState, Option<(usize, usize, usize)>>,
+    ) -> Option<usize> {
+        if closed == 0 {
+            return Some(0);
+        }
+        let state = (pos, minutes_remaining, closed);
+        if let Some(choice) = choices.get(&state) {
+            return choice.map(|(released, ..)| released);
         }
+        let choice = (0..self.valves.len())
+            .filter(|next| closed & (1 << next) != 0)
+            .filter_map(|next| {
+                let remaining = self
+                    .distance(pos, next)
+                    .and_then(|distance| minutes_remaining.checked_sub(distance + 1))
+                    .filter(|&remaining| remaining > 0)?;
+                let released =
+                    self.best_route(Some(next), remaining, closed ^ (1 << next), choices)?;
+                Some((released + remaining * self.valves[next].1, next, remaining))
+            })
+            .max_by_key(|(released, ..)| *released);
+        choices.insert(state, choice);
+        choice.map(|(released, ..)| released)
     }
 
-    fn tunnel(&self, new_pos: ValveId, minutes_remaining: usize) -> Self {
-        let pressure_released = self.pressure_released + self.total_flow_rate;
-        let total_flow_rate = self.total_flow_rate;
-        Self {
-            pressure_released,
-            total_flow_rate,
-            eventual_pressure_released: pressure_released + minutes_remaining * total_flow_rate,
-            valves_open: self.valves_open.clone(),
-            valves_closed: self.valves_closed.clone(),
-            pos: new_pos,
+    /// The most pressure that can be released, and the set of valves each actor opens for it
+    fn plan(&self, part: Part) -> (usize, Vec<usize>) {
+        match part {
+            Part::One => {
+                let best = self.best_by_valves(MINUTES);
+                let (opened, released) = best
+                    .into_iter()
+                    .enumerate()
+                    .max_by_key(|(_, released)| *released)
+                    .unwrap();
+                (released, vec![opened])
+            },
+            Part::Two => {
+                let best = best_within(self.best_by_valves(MINUTES - ELEPHANT_TRAINING));
+                let all = best.len() - 1;
+                let yours = (0..best.len())
+                    .max_by_key(|&yours| best[yours].0 + best[all ^ yours].0)
+                    .unwrap();
+                let (yours, elephants) = (best[yours], best[all ^ yours]);
+                (yours.0 + elephants.0, vec![yours.1, elephants.1])
+            },
         }
     }
+}
 
-    fn open_valve(&self, rates: &ValveRate, minutes_remaining: usize) -> Self {
-        let mut valves_open = self.valves_open.clone();
-        let mut valves_closed = self.valves_closed.clone();
-        assert!(valves_open.insert(self.pos.clone()));
-        assert!(valves_closed.remove(rates.0.get(&self.pos).unwrap()));
-        let pressure_released = self.pressure_released + self.total_flow_rate;
-        let total_flow_rate = self.total_flow_rate + rates.0.get(&self.pos).unwrap().rate;
-        Self {
-            pressure_released,
-            total_flow_rate,
-            eventual_pressure_released: pressure_released + minutes_remaining * total_flow_rate,
-            valves_open,
-            valves_closed,
-            pos: self.pos.clone(),
+/// The best of each set of valves and all its subsets, with the subset that achieves it, from the
+/// best of each exact set, so that whoever opens a set of valves need not open all of them
+fn best_within(best: Vec<usize>) -> Vec<(usize, usize)> {
+    let valves = best.len().trailing_zeros();
+    let mut within: Vec<(usize, usize)> = best.into_iter().zip(0..).collect();
+    for valve in 0..valves {
+        for opened in 0..within.len() {
+            if opened & (1 << valve) != 0 && within[opened ^ (1 << valve)].0 > within[opened].0 {
+                within[opened] = within[opened ^ (1 << valve)];
+            }
         }
     }
+    within
+}
 
-    fn best_case_pressure_release(&self, minutes_remaining: usize) -> usize {
-        let best_valves_remaining_release: usize = self
-            .valves_closed
-            .iter()
-            .take(minutes_remaining.checked_div(2).unwrap())
-            .enumerate()
-            .map(|(idx, v)| v.rate * (minutes_remaining - 1 - (2 * idx)))
-            .sum();
-        self.eventual_pressure_released + best_valves_remaining_release
+pub fn part1(valves: &ValveEntries, _params: &()) -> SolveResult<PartOutput<usize>> {
+    let network = Network::new(valves)?;
+    log::debug!("{network:?}");
+    Ok(PartOutput {
+        answer: network.plan(Part::One).0,
+    })
+}
+
+/// You and the elephant open different valves, so the best plan splits the valves in two
+pub fn part2(valves: &ValveEntries, _params: &()) -> SolveResult<PartOutput<usize>> {
+    let network = Network::new(valves)?;
+    Ok(PartOutput {
+        answer: network.plan(Part::Two).0,
+    })
+}
+
+/// What you or the elephant do during a minute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Walk through a tunnel to this valve
+    Move(ValveId),
+    Open(ValveId),
+    Wait,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Move(valve) => write!(f, "move to {valve}"),
+            Self::Open(valve) => write!(f, "open {valve}"),
+            Self::Wait => write!(f, "wait"),
+        }
     }
 }
 
-pub fn part1(valves: &ValveEntries) -> PartOutput<usize> {
-    let adjacency: HashMap<_, _> = valves
+/// A minute of the best plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub minute: usize,
+    /// What you do, then what the elephant does in part 2
+    pub actions: Vec<Action>,
+    /// The pressure released by the end of the minute
+    pub pressure_released: usize,
+}
+
+/// The best plan for a part, minute by minute
+pub fn schedule(valves: &ValveEntries, part: Part) -> SolveResult<Vec<Step>> {
+    let network = Network::new(valves)?;
+    let minutes = match part {
+        Part::One => MINUTES,
+        Part::Two => MINUTES - ELEPHANT_TRAINING,
+    };
+    let adjacency: HashMap<&ValveId, &[ValveId]> = valves
         .0
         .iter()
-        .map(|v| (v.identifier.clone(), v.connections.clone()))
+        .map(|v| (&v.identifier, v.connections.as_slice()))
         .collect();
-    let rates = ValveRate(
-        valves
-            .0
-            .iter()
-            .map(|v| {
-                (
-                    v.identifier.clone(),
-                    Valve {
-                        identifier: v.identifier.clone(),
-                        rate: v.rate,
-                    },
+    let rates: HashMap<&ValveId, usize> =
+        valves.0.iter().map(|v| (&v.identifier, v.rate)).collect();
+
+    // The plan should always be retraceable, but an explanation is not worth a panic
+    let mismatch = |problem: String| {
+        SolveError::Unsupported(format!("the best plan cannot be retraced, {problem}"))
+    };
+    let (released, plan) = network.plan(part);
+    let timelines: Vec<Vec<Action>> = plan
+        .into_iter()
+        .map(|opened| {
+            let (_, order) = network
+                .route(minutes, opened)
+                .ok_or_else(|| mismatch(String::from("its valves cannot all be opened in time")))?;
+            let mut actions = Vec::new();
+            let mut pos = &START;
+            for next in order {
+                let valve = &network.valves[next].0;
+                let path = bfs(
+                    pos,
+                    |valve| adjacency[valve].iter().cloned(),
+                    |reached| reached == valve,
                 )
-            })
-            .collect(),
-    );
-
-    // A* Best Case
-
-    // BFS
-    let mut states = BinaryHeap::from([State::init(&rates)]);
-    for minute in 1..=30 {
-        let minutes_remaining = 30 - minute;
-        let drained = std::mem::take(&mut states);
-        for state in drained {
-            if state.valves_closed.is_empty() {
-                states.push(state.noop(minutes_remaining));
-                continue;
+                .ok_or_else(|| mismatch(format!("there is no way from {pos} to {valve}")))?;
+                actions.extend(path.into_iter().skip(1).map(Action::Move));
+                actions.push(Action::Open(valve.clone()));
+                pos = valve;
             }
-            for adjacent in adjacency.get(&state.pos).unwrap() {
-                states.push(state.tunnel(adjacent.clone(), minutes_remaining))
+            actions.resize(minutes, Action::Wait);
+            Ok(actions)
+        })
+        .collect::<SolveResult<_>>()?;
+
+    let mut flow_rate = 0;
+    let mut pressure_released = 0;
+    let steps: Vec<Step> = (0..minutes)
+        .map(|minute| {
+            // a valve only releases pressure from the minute after it is opened
+            pressure_released += flow_rate;
+            let actions: Vec<Action> = timelines
+                .iter()
+                .map(|actions| actions[minute].clone())
+                .collect();
+            for action in &actions {
+                if let Action::Open(valve) = action {
+                    flow_rate += rates[valve];
+                }
             }
-            if state
-                .valves_closed
-                .contains(rates.0.get(&state.pos).unwrap())
-            {
-                states.push(state.open_valve(&rates, minutes_remaining))
+            Step {
+                minute: minute + 1,
+                actions,
+                pressure_released,
             }
-        }
-        log::debug!("{} states", states.len());
-        if states.len() > 1 {
-            let best_eventual_pressure_released = states.peek().unwrap().eventual_pressure_released;
-            log::debug!("best_eventual_pressure_released {best_eventual_pressure_released}");
-            log::debug!(
-                "corresponding best_case {}",
-                states
-                    .peek()
-                    .unwrap()
-                    .best_case_pressure_release(minutes_remaining)
-            );
-            let states_len = states.len();
-            states.retain(|s| {
-                s.best_case_pressure_release(minutes_remaining) >= best_eventual_pressure_released
-            });
-            log::debug!("reduced {} to {} states", states_len, states.len());
-        }
-        log::trace!(
-            "states: {:?}",
-            states
-                .iter()
-                .map(|s| s.pressure_released)
-                .collect::<Vec<_>>()
-        );
-    }
-    let best_pressure_released = states.iter().map(|s| s.pressure_released).max().unwrap();
-    PartOutput {
-        answer: best_pressure_released,
+        })
+        .collect();
+    if pressure_released != released {
+        return Err(mismatch(format!(
+            "it releases {pressure_released} pressure rather than {released}"
+        )));
     }
+    Ok(steps)
 }
 
-pub fn part2(_something: &ValveEntries) -> PartOutput<usize> {
-    PartOutput { answer: 0 }
+pub fn explain(valves: &ValveEntries, _params: &(), part: Part) -> SolveResult<Explanation> {
+    let mut headings = vec![String::from("Minute"), String::from("You")];
+    if part == Part::Two {
+        headings.push(String::from("Elephant"));
+    }
+    headings.push(String::from("Pressure released"));
+    let rows = schedule(valves, part)?
+        .into_iter()
+        .map(|step| {
+            std::iter::once(step.minute.to_string())
+                .chain(step.actions.iter().map(ToString::to_string))
+                .chain(std::iter::once(step.pressure_released.to_string()))
+                .collect()
+        })
+        .collect();
+    Ok(Explanation { headings, rows })
 }
 
 pub const DAY: Day<ValveEntries, usize> = Day {
-    title: "TITLE",
+    title: "Proboscidea Volcanium",
     display: (
-        "Foobar foobar foobar {answer}",
-        "Foobar foobar foobar {answer}",
+        "The most pressure you can release is {answer}",
+        "The most pressure you and the elephant can release is {answer}",
     ),
     calc: DayCalc {
         parse,
         part1,
         part2,
+        explain: Some(explain),
     },
-    example: include_str!("../../examples/day16.in.txt"),
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day16.in.txt"),
+    )],
+    params: NO_PARAMS,
 };
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn test_network_distances() {
+        let entries = parse(DAY.examples[0].input).unwrap();
+        let network = Network::new(&entries).unwrap();
+        let index = |id: [char; 2]| {
+            network
+                .valves
+                .iter()
+                .position(|(valve, _)| *valve == ValveId(id))
+                .unwrap()
+        };
+        // AA, II and JJ have no flow, so only the six other valves are kept
+        assert_eq!(network.valves.len(), 6);
+        assert_eq!(network.from_start[index(['J', 'J'])], Some(2));
+        assert_eq!(
+            network.distance(Some(index(['H', 'H'])), index(['B', 'B'])),
+            Some(6)
+        );
+        assert_eq!(
+            network.distance(Some(index(['D', 'D'])), index(['D', 'D'])),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_schedule() {
+        let entries = parse(DAY.examples[0].input).unwrap();
+        let steps = schedule(&entries, Part::One).unwrap();
+        assert_eq!(steps.len(), MINUTES);
+        let opened: Vec<String> = steps
+            .iter()
+            .filter_map(|step| match &step.actions[0] {
+                Action::Open(valve) => Some(valve.to_string()),
+                _ => None,
+            })
+            .collect();
+        // the order the puzzle's walkthrough opens them in
+        assert_eq!(opened, ["DD", "BB", "JJ", "HH", "EE", "CC"]);
+        assert_eq!(steps[2].pressure_released, 20);
+        assert_eq!(steps.last().unwrap().pressure_released, 1651);
+
+        let steps = schedule(&entries, Part::Two).unwrap();
+        assert_eq!(steps.len(), MINUTES - ELEPHANT_TRAINING);
+        assert!(steps.iter().all(|step| step.actions.len() == 2));
+        assert_eq!(steps.last().unwrap().pressure_released, 1707);
+        let explanation = explain(&entries, &(), Part::Two).unwrap();
+        assert_eq!(
+            explanation.headings,
+            ["Minute", "You", "Elephant", "Pressure released"]
+        );
+        assert_eq!(explanation.rows[0][0], "1");
+    }
+
+    #[test]
+    fn test_too_many_valves() {
+        let input: String = (0..=MAX_VALVES as u8)
+            .map(|n| {
+                format!(
+                    "Valve V{} has flow rate=1; tunnel leads to valve AA\n",
+                    char::from(b'A' + n)
+                )
+            })
+            .chain(["Valve AA has flow rate=0; tunnel leads to valve VA\n".to_owned()])
+            .collect();
+        let entries = parse(&input).unwrap();
+        assert!(matches!(
+            part1(&entries, &()),
+            Err(SolveError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_valve() {
+        let input = "Valve AA has flow rate=0; tunnel leads to valve BB\n\
+                     Valve BB has flow rate=5; tunnel leads to valve AA\n\
+                     Valve BB has flow rate=7; tunnel leads to valve AA\n";
+        let error = parse(input).unwrap_err();
+        assert_eq!(error.location().unwrap().line, 3);
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::params::NO_PARAMS;
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
+
+/// The chamber is seven units wide, with each row a bitmask where bit `x` is column `x`
+const WIDTH: usize = 7;
+/// How far below the top of the tower its surface is followed down each column
+const SURFACE_DEPTH: usize = 64;
+/// How many rocks may fall before the tower has to start repeating
+const MAX_ROCKS_BEFORE_CYCLE: u64 = 100_000;
+
+/// The rocks in the order they fall, each row from the bottom up, against the left wall
+const ROCKS: [&[u8]; 5] = [
+    &[0b1111],
+    &[0b010, 0b111, 0b010],
+    &[0b111, 0b100, 0b100],
+    &[0b1, 0b1, 0b1, 0b1],
+    &[0b11, 0b11],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+pub struct Jets(Vec<Jet>);
+
+impl FromStr for Jets {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern = s.trim();
+        pattern
+            .char_indices()
+            .map(|(idx, c)| match c {
+                '<' => Ok(Jet::Left),
+                '>' => Ok(Jet::Right),
+                _ => Err(
+                    ParseError::Str(format!("unknown jet {c:?}, expected '<' or '>'"))
+                        .locate(s, &pattern[idx..idx + c.len_utf8()]),
+                ),
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+pub fn parse(input: &str) -> ParseResult<Jets> {
+    input.parse()
+}
+
+/// The rock `rows` pushed one unit by a jet, unless a wall is in the way
+fn push(rows: &[u8], jet: Jet) -> Option<Vec<u8>> {
+    rows.iter()
+        .map(|&row| match jet {
+            Jet::Left => (row & 1 == 0).then_some(row >> 1),
+            Jet::Right => (row & (1 << (WIDTH - 1)) == 0).then_some(row << 1),
+        })
+        .collect()
+}
+
+/// The settled rocks, with the position in the jet pattern and the order of rocks
+struct Chamber<'j> {
+    rows: Vec<u8>,
+    jets: &'j [Jet],
+    jet_index: usize,
+    rock_index: usize,
+}
+
+impl<'j> Chamber<'j> {
+    fn new(jets: &'j Jets) -> Self {
+        Self {
+            rows: Vec::new(),
+            jets: &jets.0,
+            jet_index: 0,
+            rock_index: 0,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the rock `rows` would overlap a settled rock with its bottom at `y`
+    fn collides(&self, rock: &[u8], y: usize) -> bool {
+        rock.iter()
+            .zip(y..)
+            .any(|(row, y)| matches!(self.rows.get(y), Some(settled) if settled & row != 0))
+    }
+
+    /// Drop the next rock, pushed by the jets until it comes to rest
+    fn drop_rock(&mut self) {
+        // each rock appears two units from the left wall and three units above the tower
+        let mut rock: Vec<u8> = ROCKS[self.rock_index].iter().map(|row| row << 2).collect();
+        self.rock_index = (self.rock_index + 1) % ROCKS.len();
+        let mut y = self.height() + 3;
+        loop {
+            let jet = self.jets[self.jet_index];
+            self.jet_index = (self.jet_index + 1) % self.jets.len();
+            if let Some(pushed) = push(&rock, jet).filter(|pushed| !self.collides(pushed, y)) {
+                rock = pushed;
+            }
+            if y == 0 || self.collides(&rock, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        for (row, y) in rock.into_iter().zip(y..) {
+            if y == self.height() {
+                self.rows.push(0);
+            }
+            self.rows[y] |= row;
+        }
+    }
+
+    /// How far below the top of the tower each column's highest rock is, or `None` if a
+    /// column is open deeper than [`SURFACE_DEPTH`]
+    fn surface(&self) -> Option<[usize; WIDTH]> {
+        let mut surface = [0; WIDTH];
+        for (x, depth) in surface.iter_mut().enumerate() {
+            *depth = self
+                .rows
+                .iter()
+                .rev()
+                .take(SURFACE_DEPTH)
+                .position(|row| row & (1 << x) != 0)
+                .or_else(|| (self.height() <= SURFACE_DEPTH).then_some(self.height()))?;
+        }
+        Some(surface)
+    }
+
+    /// The top `rows` rows of the tower, drawn the way the puzzle does
+    fn render(&self, rows: usize) -> String {
+        let mut lines: Vec<String> = self
+            .rows
+            .iter()
+            .rev()
+            .take(rows)
+            .map(|row| {
+                let cells: String = (0..WIDTH)
+                    .map(|x| if row & (1 << x) != 0 { '#' } else { '.' })
+                    .collect();
+                format!("|{cells}|")
+            })
+            .collect();
+        if rows >= self.height() {
+            lines.push(format!("+{}+", "-".repeat(WIDTH)));
+        }
+        lines.join("\n")
+    }
+}
+
+/// How tall the tower is once `rocks` rocks have fallen.
+///
+/// Once the next rock, the next jet and the shape of the top of the tower repeat, so does
+/// everything after them, so whole cycles are skipped by adding the height each one adds.
+/// Jets that keep a column open never give the top of the tower a shape that can repeat.
+fn tower_height(jets: &Jets, rocks: u64) -> SolveResult<u64> {
+    let mut chamber = Chamber::new(jets);
+    let mut seen: HashMap<(usize, usize, [usize; WIDTH]), (u64, usize)> = HashMap::new();
+    let mut skipped_height = None;
+    let mut dropped = 0;
+    while dropped < rocks {
+        chamber.drop_rock();
+        dropped += 1;
+        if skipped_height.is_some() {
+            continue;
+        }
+        if let Some(surface) = chamber.surface() {
+            let state = (chamber.rock_index, chamber.jet_index, surface);
+            if let Some((cycle_start, start_height)) =
+                seen.insert(state, (dropped, chamber.height()))
+            {
+                let cycle = dropped - cycle_start;
+                let growth = (chamber.height() - start_height) as u64;
+                let cycles = (rocks - dropped) / cycle;
+                log::debug!(
+                    "every {cycle} rocks from rock {cycle_start} add {growth} rows, skipping {cycles} cycles:\n{}",
+                    chamber.render(10)
+                );
+                dropped += cycles * cycle;
+                skipped_height = Some(cycles * growth);
+                continue;
+            }
+        }
+        if dropped >= MAX_ROCKS_BEFORE_CYCLE {
+            return Err(SolveError::Unsupported(format!(
+                "the tower did not start repeating within {MAX_ROCKS_BEFORE_CYCLE} rocks"
+            )));
+        }
+    }
+    log::debug!(
+        "after {rocks} rocks the tower is {} rows tall, with {} skipped:\n{}",
+        chamber.height(),
+        skipped_height.unwrap_or_default(),
+        chamber.render(10)
+    );
+    Ok(chamber.height() as u64 + skipped_height.unwrap_or_default())
+}
+
+pub fn part1(jets: &Jets, _params: &()) -> SolveResult<PartOutput<u64>> {
+    Ok(PartOutput {
+        answer: tower_height(jets, 2022)?,
+    })
+}
+
+pub fn part2(jets: &Jets, _params: &()) -> SolveResult<PartOutput<u64>> {
+    Ok(PartOutput {
+        answer: tower_height(jets, 1_000_000_000_000)?,
+    })
+}
+
+pub const DAY: Day<Jets, u64> = Day {
+    title: "Pyroclastic Flow",
+    display: (
+        "The tower of rocks is {answer} units tall after 2022 rocks have stopped falling",
+        "The tower of rocks is {answer} units tall after 1000000000000 rocks have stopped falling",
+    ),
+    calc: DayCalc {
+        parse,
+        part1,
+        part2,
+        explain: None,
+    },
+    examples: &[Example::new(
+        "main",
+        include_str!("../../examples/day17.in.txt"),
+    )],
+    params: NO_PARAMS,
+};
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let jets = parse(DAY.examples[0].input).unwrap();
+        let mut chamber = Chamber::new(&jets);
+        for _ in 0..3 {
+            chamber.drop_rock();
+        }
+        // the tower after the third rock in the puzzle's walkthrough
+        assert_eq!(
+            chamber.render(10),
+            [
+                "|..#....|",
+                "|..#....|",
+                "|####...|",
+                "|..###..|",
+                "|...#...|",
+                "|..####.|",
+                "+-------+",
+            ]
+            .join("\n")
+        );
+        assert_eq!(chamber.render(2), "|..#....|\n|..#....|");
+        assert!(chamber.rows.iter().all(|row| row >> WIDTH == 0));
+    }
+
+    #[test]
+    fn test_cycles_match_simulation() {
+        let jets = parse(DAY.examples[0].input).unwrap();
+        let mut chamber = Chamber::new(&jets);
+        for rocks in 1..=3000 {
+            chamber.drop_rock();
+            assert_eq!(
+                tower_height(&jets, rocks).unwrap(),
+                chamber.height() as u64,
+                "after {rocks} rocks"
+            );
+        }
+    }
+
+    #[test]
+    fn test_open_column() {
+        // pushed against the left wall, no rock ever reaches the rightmost columns
+        let jets = parse("<").unwrap();
+        let mut chamber = Chamber::new(&jets);
+        for _ in 0..2022 {
+            chamber.drop_rock();
+        }
+        assert!(chamber.surface().is_none());
+        assert_eq!(tower_height(&jets, 2022).unwrap(), chamber.height() as u64);
+    }
+}
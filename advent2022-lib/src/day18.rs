@@ -0,0 +1,116 @@
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+
+use crate::params::NO_PARAMS;
+use crate::parser::parse_lines;
+use crate::point3d::{Bounds, Point3};
+use crate::{Day, DayCalc, Example, ParseError, ParseResult, PartOutput, SolveError, SolveResult};
+
+/// The flood fill visits every point around the droplet, so its bounds can only be so large
+const MAX_VOLUME: usize = 1 << 24;
+
+/// The 1x1x1 cubes scanned in the lava droplet
+#[derive(Debug)]
+pub struct Droplet(HashSet<Point3>);
+
+impl FromStr for Droplet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse_lines::<Point3>(s)?.into_iter().collect()))
+    }
+}
+
+pub fn parse(input: &str) -> ParseResult<Droplet> {
+    input.parse()
+}
+
+/// Faces of cubes that are not against another cube, including those of air pockets inside
+pub fn part1(droplet: &Droplet, _params: &()) -> SolveResult<PartOutput<usize>> {
+    Ok(PartOutput {
+        answer: droplet
+            .0
+            .iter()
+            .map(|cube| 6 - cube.neighbours().filter(|n| droplet.0.contains(n)).count())
+            .sum(),
+    })
+}
+
+/// Faces that steam can reach, found by filling the box around the droplet from one corner
+pub fn part2(droplet: &Droplet, _params: &()) -> SolveResult<PartOutput<usize>> {
+    let Some(bounds) = Bounds::of(&droplet.0) else {
+        return Ok(PartOutput { answer: 0 });
+    };
+    let bounds = bounds
+        .grow(1)
+        .filter(|bounds| matches!(bounds.volume(), Some(volume) if volume <= MAX_VOLUME))
+        .ok_or_else(|| {
+            SolveError::Unsupported(format!(
+                "the droplet is too large to fill around, at most {MAX_VOLUME} cubes can be"
+            ))
+        })?;
+    let mut outside = HashSet::from([bounds.min]);
+    let mut queue = VecDeque::from([bounds.min]);
+    let mut faces = 0;
+    while let Some(air) = queue.pop_front() {
+        for next in air.neighbours().filter(|next| bounds.contains(next)) {
+            if droplet.0.contains(&next) {
+                faces += 1;
+            } else if outside.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    log::debug!(
+        "{} of the {:?} cubes around the droplet are outside it",
+        outside.len(),
+        bounds.volume()
+    );
+    Ok(PartOutput { answer: faces })
+}
+
+pub const DAY: Day<Droplet, usize> = Day {
+    title: "Boiling Boulders",
+    display: (
+        "The surface area of the scanned lava droplet is {answer}",
+        "The exterior surface area of the scanned lava droplet is {answer}",
+    ),
+    calc: DayCalc {
+        parse,
+        part1,
+        part2,
+        explain: None,
+    },
+    examples: &[
+        Example::new("main", include_str!("../../examples/day18.in.txt")),
+        // The two adjacent cubes the puzzle starts with
+        Example::new("pair", include_str!("../../examples/day18-pair.in.txt")),
+    ],
+    params: NO_PARAMS,
+};
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn test_air_pocket() {
+        // a 3x3x3 cube with its centre missing hides the six faces around the centre
+        let input: String = (0..27)
+            .filter(|&n| n != 13)
+            .map(|n| format!("{},{},{}\n", n % 3, n / 3 % 3, n / 9))
+            .collect();
+        let droplet = parse(&input).unwrap();
+        assert_eq!(part1(&droplet, &()).unwrap().answer, 6 * 9 + 6);
+        assert_eq!(part2(&droplet, &()).unwrap().answer, 6 * 9);
+    }
+
+    #[test]
+    fn test_empty() {
+        let droplet = parse("").unwrap();
+        assert_eq!(part1(&droplet, &()).unwrap().answer, 0);
+        assert_eq!(part2(&droplet, &()).unwrap().answer, 0);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::params::ParamSettings;
+use crate::{get_days, BOTH_PARTS};
+
+/// Run both parts of a day on arbitrary bytes, as the fuzz targets in `fuzz/` do.
+///
+/// The bytes are decoded the way the web app decodes uploaded files, replacing invalid UTF-8.
+/// Days run with the parameters for examples, since those for real inputs, like day 15's row
+/// 2000000, make every run slow enough to be reported as a timeout.
+/// Errors are expected answers to bad input, so only a panic is a bug.
+pub fn run(day_num: usize, data: &[u8]) {
+    let days = get_days();
+    let day = days
+        .get(&day_num)
+        .unwrap_or_else(|| panic!("day {day_num} is not registered"));
+    let input = String::from_utf8_lossy(data);
+    let _ = day.run_with(&input, &BOTH_PARTS, &ParamSettings::example());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{read, read_dir};
+    use std::panic::catch_unwind;
+    use std::path::Path;
+
+    use test_log::test;
+
+    use super::*;
+
+    /// Replay the crashes cargo-fuzz saved in `fuzz/artifacts/dayNN/`, so that each one stays
+    /// fixed once it is kept there
+    #[test]
+    fn test_replay_crashes() {
+        let mut failures = Vec::new();
+        for day_num in get_days().into_keys() {
+            let dir = Path::new("../fuzz/artifacts").join(format!("day{day_num:02}"));
+            let entries = match read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for entry in entries {
+                let entry = entry.unwrap();
+                if !entry.file_name().to_string_lossy().starts_with("crash-") {
+                    continue;
+                }
+                let path = entry.path();
+                log::info!("replaying {}", path.display());
+                let data = read(&path).unwrap();
+                if catch_unwind(|| run(day_num, &data)).is_err() {
+                    failures.push(path.display().to_string());
+                }
+            }
+        }
+        assert!(failures.is_empty(), "crashes reproduced: {failures:?}");
+    }
+}
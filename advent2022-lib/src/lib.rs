@@ -1,31 +1,27 @@
-#![feature(binary_heap_retain)]
-
 use std::collections::btree_map::BTreeMap;
-use std::fmt::Display;
-use std::fs;
+use std::fmt::{Debug, Display};
 use std::num::ParseIntError;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::Duration;
+use std::{env, fs, io};
 
+use instant::Instant;
+use params::{DayParams, ParamError, ParamInfo, ParamSettings, Params};
 use recap::Error as RecapError;
 use strum::ParseError as StrumParseError;
+use strum_macros::{Display as StrumDisplay, EnumString};
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
+pub mod check;
+#[cfg(test)]
+#[path = "day00_template.rs"]
+mod day00_template;
+pub mod fuzz;
+pub mod ocr;
+pub mod params;
 pub mod parser;
+pub mod point3d;
 mod test;
 
 #[derive(Debug)]
@@ -35,6 +31,103 @@ pub enum ParseError {
     Str(String),
     Strum(StrumParseError),
     Recap(RecapError),
+    Param(ParamError),
+    Located {
+        location: Location,
+        reason: Box<ParseError>,
+    },
+}
+
+/// Where in the input a [`ParseError`] was raised
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// 1-based line number
+    pub line: usize,
+    /// 0-based character columns within the line
+    pub columns: Range<usize>,
+    /// The full text of the offending line
+    pub text: String,
+}
+
+impl ParseError {
+    /// Attach the position of `fragment` within `input` to the error.
+    ///
+    /// `fragment` should be a slice of `input`. If the error was already located relative to
+    /// `fragment`, that location is rebased onto `input`, so parsers can locate errors within
+    /// whatever part of the input they were given.
+    pub fn locate(self, input: &str, fragment: &str) -> Self {
+        let offset = match fragment_offset(input, fragment) {
+            Some(offset) => offset,
+            None => return self,
+        };
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line = input[..offset].matches('\n').count() + 1;
+        let column = input[line_start..offset].chars().count();
+        let text = input[line_start..].lines().next().unwrap_or_default();
+        match self {
+            Self::Located { location, reason } => {
+                let location = if location.line == 1 {
+                    Location {
+                        line,
+                        columns: column + location.columns.start..column + location.columns.end,
+                        text: text.to_owned(),
+                    }
+                } else {
+                    Location {
+                        line: line + location.line - 1,
+                        ..location
+                    }
+                };
+                Self::Located { location, reason }
+            },
+            reason => {
+                let width = fragment.lines().next().unwrap_or_default().chars().count();
+                Self::Located {
+                    location: Location {
+                        line,
+                        columns: column..column + std::cmp::max(width, 1),
+                        text: text.to_owned(),
+                    },
+                    reason: Box::new(reason),
+                }
+            },
+        }
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Self::Located { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    /// The underlying reason, without any location
+    pub fn reason(&self) -> &Self {
+        match self {
+            Self::Located { reason, .. } => reason.reason(),
+            reason => reason,
+        }
+    }
+}
+
+/// Byte offset of `fragment` within `input`, preferring the slice position over a text search.
+fn fragment_offset(input: &str, fragment: &str) -> Option<usize> {
+    let input_start = input.as_ptr() as usize;
+    let fragment_start = fragment.as_ptr() as usize;
+    if fragment_start >= input_start
+        && fragment_start + fragment.len() <= input_start + input.len()
+        && input.is_char_boundary(fragment_start - input_start)
+    {
+        Some(fragment_start - input_start)
+    } else {
+        input.find(fragment)
+    }
+}
+
+impl From<ParamError> for ParseError {
+    fn from(value: ParamError) -> Self {
+        Self::Param(value)
+    }
 }
 
 impl From<ParseIntError> for ParseError {
@@ -55,114 +148,556 @@ impl From<RecapError> for ParseError {
     }
 }
 
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        if self.columns.len() > 1 {
+            writeln!(
+                f,
+                "{gutter}--> line {}, columns {}-{}",
+                self.line,
+                self.columns.start + 1,
+                self.columns.end
+            )?;
+        } else {
+            writeln!(
+                f,
+                "{gutter}--> line {}, column {}",
+                self.line,
+                self.columns.start + 1
+            )?;
+        }
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.columns.start),
+            "^".repeat(self.columns.len())
+        )
+    }
+}
+
 impl Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "invalid input for day")
+        match self {
+            Self::Empty => write!(f, "empty input"),
+            Self::Int(e) => write!(f, "invalid integer: {e}"),
+            Self::Str(s) => write!(f, "{s}"),
+            Self::Strum(e) => write!(f, "unrecognised value: {e}"),
+            Self::Recap(e) => write!(f, "unexpected format: {e}"),
+            Self::Param(e) => write!(f, "invalid parameter: {e}"),
+            Self::Located { location, reason } => write!(f, "{reason}\n{location}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Int(e) => Some(e),
+            Self::Strum(e) => Some(e),
+            Self::Recap(e) => Some(e),
+            Self::Param(e) => Some(e),
+            Self::Located { reason, .. } => Some(reason.as_ref()),
+            Self::Empty | Self::Str(_) => None,
+        }
     }
 }
 
 pub type ParseResult<D> = std::result::Result<D, ParseError>;
 
+#[derive(Debug)]
+pub enum SolveError {
+    /// The input is valid, but the puzzle has no answer for it
+    NoSolution(String),
+    /// The input is valid, but breaks an assumption the solver relies on
+    Unsupported(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NoSolution(s) => write!(f, "no solution: {s}"),
+            Self::Unsupported(s) => write!(f, "unsupported input: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+pub type SolveResult<O> = std::result::Result<O, SolveError>;
+
 #[derive(Debug, Default)]
 pub struct PartOutput<O> {
     pub answer: O,
 }
 
-pub struct DayCalc<D, O> {
+/// How a part's answer was found, as a table with a row for each step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub headings: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let widths: Vec<usize> = (0..self.headings.len())
+            .map(|column| {
+                std::iter::once(&self.headings)
+                    .chain(&self.rows)
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        let lines: Vec<String> = std::iter::once(&self.headings)
+            .chain(&self.rows)
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:width$}"))
+                    .collect();
+                cells.join("  ").trim_end().to_owned()
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub type ExplainFunc<D, P> = fn(&D, &P, Part) -> SolveResult<Explanation>;
+
+pub struct DayCalc<D, O, P = ()> {
     pub parse: fn(&str) -> ParseResult<D>,
-    pub part1: fn(&D) -> PartOutput<O>,
-    pub part2: fn(&D) -> PartOutput<O>,
+    pub part1: fn(&D, &P) -> SolveResult<PartOutput<O>>,
+    pub part2: fn(&D, &P) -> SolveResult<PartOutput<O>>,
+    /// Show how either part's answer is found, for the days that can
+    pub explain: Option<ExplainFunc<D, P>>,
 }
 
-pub struct Day<D, O> {
+pub struct Day<D, O, P = ()> {
     pub title: &'static str,
     pub display: (&'static str, &'static str),
-    pub calc: DayCalc<D, O>,
-    pub example: &'static str,
+    pub calc: DayCalc<D, O, P>,
+    pub examples: &'static [Example],
+    pub params: DayParams<P>,
+}
+
+/// One of the examples in a day's puzzle text.
+///
+/// The first example of day NN is `examples/dayNN.in.txt`, and any others are
+/// `examples/dayNN-NAME.in.txt`, each with its expected answers in the matching `.out.txt`.
+#[derive(Debug)]
+pub struct Example {
+    pub name: &'static str,
+    pub input: &'static str,
+    /// Parameters this example was written for, on top of the day's example parameters
+    pub params: &'static [(&'static str, &'static str)],
+}
+
+impl Example {
+    pub const fn new(name: &'static str, input: &'static str) -> Self {
+        Self {
+            name,
+            input,
+            params: &[],
+        }
+    }
+
+    pub const fn with_params(self, params: &'static [(&'static str, &'static str)]) -> Self {
+        Self { params, ..self }
+    }
+
+    /// The settings to run this example with, followed by any further overrides
+    pub fn settings(&self, overrides: &[(String, String)]) -> ParamSettings {
+        let mut settings = ParamSettings::example();
+        settings.overrides = self
+            .params
+            .iter()
+            .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+            .chain(overrides.iter().cloned())
+            .collect();
+        settings
+    }
+}
+
+/// The file name of a day's example without its `.in.txt` or `.out.txt` extension
+pub fn example_file_stem(day_num: usize, index: usize, example: &Example) -> String {
+    match index {
+        0 => format!("day{:02}", day_num),
+        _ => format!("day{:02}-{}", day_num, example.name),
+    }
 }
 
 pub trait Printable {
     fn get_display(&self) -> (&'static str, &'static str);
     fn get_title(&self) -> &'static str;
-    fn get_example(&self) -> &'static str;
+    fn get_examples(&self) -> &'static [Example];
+    /// The input of the day's first example
+    fn get_example(&self) -> &'static str {
+        self.get_examples()[0].input
+    }
+    /// Find an example by its name, or by its position counting from 1
+    fn find_example(&self, selector: &str) -> Option<(usize, &'static Example)> {
+        let examples = self.get_examples();
+        examples
+            .iter()
+            .position(|example| example.name == selector)
+            .or_else(|| {
+                selector
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&position| (1..=examples.len()).contains(&position))
+                    .map(|position| position - 1)
+            })
+            .map(|index| (index, &examples[index]))
+    }
+    /// The parameters a run with these settings would use
+    fn get_params(&self, settings: &ParamSettings) -> Result<Vec<ParamInfo>, ParamError>;
 }
 
-impl<D, O> Printable for Day<D, O> {
+impl<D, O, P: Params + Clone> Printable for Day<D, O, P> {
     fn get_display(&self) -> (&'static str, &'static str) {
         self.display
     }
     fn get_title(&self) -> &'static str {
         self.title
     }
-    fn get_example(&self) -> &'static str {
-        self.example
+    fn get_examples(&self) -> &'static [Example] {
+        self.examples
+    }
+    fn get_params(&self, settings: &ParamSettings) -> Result<Vec<ParamInfo>, ParamError> {
+        settings.apply(&self.params).map(|params| params.describe())
     }
 }
 
-type DayResult = ParseResult<(String, String)>;
+/// One of the two parts of a day's puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, StrumDisplay)]
+pub enum Part {
+    #[strum(serialize = "1")]
+    One,
+    #[strum(serialize = "2")]
+    Two,
+}
 
-pub trait Calculable {
-    fn both(&self, input: &str) -> DayResult;
-    fn get_both_func(&self) -> Rc<dyn Fn(&str) -> DayResult>;
+/// The answer to one part of a day, and how long it took to solve
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: SolveResult<String>,
+    pub duration: Duration,
+}
+
+/// The parts that were run for a day's input, and how long parsing took
+#[derive(Debug)]
+pub struct DayRun {
+    pub parse_duration: Duration,
+    pub parts: Vec<PartRun>,
 }
 
-impl<D: 'static, O: 'static + std::fmt::Display> Calculable for Day<D, O> {
+impl DayRun {
+    pub fn part(&self, part: Part) -> Option<&PartRun> {
+        self.parts.iter().find(|run| run.part == part)
+    }
+
+    /// Wall-clock time spent parsing and solving
+    pub fn total_duration(&self) -> Duration {
+        self.parse_duration + self.parts.iter().map(|run| run.duration).sum::<Duration>()
+    }
+}
+
+pub type DayResult = ParseResult<DayRun>;
+
+pub const BOTH_PARTS: [Part; 2] = [Part::One, Part::Two];
+
+/// A day's parsed input and parameters, which can run either part without parsing again.
+///
+/// Its `Debug` form is that of the day's parsed structure.
+pub trait ParsedDay: Debug {
+    fn part1(&self) -> SolveResult<String>;
+    fn part2(&self) -> SolveResult<String>;
+    fn params(&self) -> Vec<ParamInfo>;
+    /// Change a parameter for the following runs, keeping the parsed input
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), ParamError>;
+    /// How the part's answer is found, or `None` if the day cannot explain itself
+    fn explain(&self, part: Part) -> Option<SolveResult<Explanation>>;
+    fn part(&self, part: Part) -> SolveResult<String> {
+        match part {
+            Part::One => self.part1(),
+            Part::Two => self.part2(),
+        }
+    }
+}
+
+pub type ParseFunc = Rc<dyn Fn(&str, &ParamSettings) -> ParseResult<Box<dyn ParsedDay>>>;
+pub type RunFunc = Rc<dyn Fn(&str, &[Part], &ParamSettings) -> DayResult>;
+pub type ParsedRunFunc =
+    Rc<dyn Fn(&str, &[Part], &ParamSettings) -> ParseResult<(Box<dyn ParsedDay>, DayRun)>>;
+
+struct Parsed<D, O, P> {
+    input: D,
+    params: P,
+    part1: fn(&D, &P) -> SolveResult<PartOutput<O>>,
+    part2: fn(&D, &P) -> SolveResult<PartOutput<O>>,
+    explain: Option<ExplainFunc<D, P>>,
+}
+
+impl<D: Debug, O, P> Debug for Parsed<D, O, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.input.fmt(f)
+    }
+}
+
+impl<D: Debug, O: Display, P: Params> ParsedDay for Parsed<D, O, P> {
+    fn part1(&self) -> SolveResult<String> {
+        (self.part1)(&self.input, &self.params).map(|output| output.answer.to_string())
+    }
+    fn part2(&self) -> SolveResult<String> {
+        (self.part2)(&self.input, &self.params).map(|output| output.answer.to_string())
+    }
+    fn params(&self) -> Vec<ParamInfo> {
+        self.params.describe()
+    }
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        self.params.set(name, value)
+    }
+    fn explain(&self, part: Part) -> Option<SolveResult<Explanation>> {
+        self.explain
+            .map(|explain| explain(&self.input, &self.params, part))
+    }
+}
+
+/// Parse the input for a day, rejecting blank input before it reaches the day's parser.
+fn parse_input<D>(parse: fn(&str) -> ParseResult<D>, input: &str) -> ParseResult<D> {
+    if input.trim().is_empty() {
+        return Err(ParseError::Empty);
+    }
+    parse(input)
+}
+
+/// Parse the input once, timing it, then time each of the selected parts, keeping the parsed
+/// input
+fn run_parts(
+    parse: impl Fn(&str, &ParamSettings) -> ParseResult<Box<dyn ParsedDay>>,
+    input: &str,
+    parts: &[Part],
+    settings: &ParamSettings,
+) -> ParseResult<(Box<dyn ParsedDay>, DayRun)> {
+    let start = Instant::now();
+    let parsed = parse(input, settings)?;
+    let parse_duration = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = parsed.part(part);
+            PartRun {
+                part,
+                answer,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+    let run = DayRun {
+        parse_duration,
+        parts,
+    };
+    Ok((parsed, run))
+}
+
+pub trait Calculable {
+    /// Parse the input once with the day's parameters for real inputs
+    fn parse(&self, input: &str) -> ParseResult<Box<dyn ParsedDay>> {
+        self.parse_with(input, &ParamSettings::default())
+    }
+    /// Parse the input once with the chosen parameters, so that each part can be run on its own
+    fn parse_with(&self, input: &str, settings: &ParamSettings) -> ParseResult<Box<dyn ParsedDay>>;
+    fn get_parse_func(&self) -> ParseFunc;
+    /// Whether the day can explain how its answers are found
+    fn can_explain(&self) -> bool;
+    /// Parse the input, then run only the selected parts
+    fn run(&self, input: &str, parts: &[Part]) -> DayResult {
+        self.run_with(input, parts, &ParamSettings::default())
+    }
+    fn run_with(&self, input: &str, parts: &[Part], settings: &ParamSettings) -> DayResult {
+        run_parts(
+            |input, settings| self.parse_with(input, settings),
+            input,
+            parts,
+            settings,
+        )
+        .map(|(_parsed, run)| run)
+    }
+    fn get_run_func(&self) -> RunFunc {
+        let run = self.get_parsed_run_func();
+        Rc::new(
+            move |input: &str, parts: &[Part], settings: &ParamSettings| {
+                run(input, parts, settings).map(|(_parsed, run)| run)
+            },
+        )
+    }
+    /// Like [`Calculable::get_run_func`], also keeping the parsed input, e.g. to explain answers
+    fn get_parsed_run_func(&self) -> ParsedRunFunc {
+        let parse = self.get_parse_func();
+        Rc::new(
+            move |input: &str, parts: &[Part], settings: &ParamSettings| {
+                run_parts(parse.as_ref(), input, parts, settings)
+            },
+        )
+    }
     fn both(&self, input: &str) -> DayResult {
-        let parse = self.calc.parse;
-        let part1 = self.calc.part1;
-        let part2 = self.calc.part2;
-        let input = parse(input)?;
-        Ok((
-            part1(&input).answer.to_string(),
-            part2(&input).answer.to_string(),
-        ))
+        self.run(input, &BOTH_PARTS)
     }
     fn get_both_func(&self) -> Rc<dyn Fn(&str) -> DayResult> {
+        let run = self.get_run_func();
+        Rc::new(move |input: &str| run(input, &BOTH_PARTS, &ParamSettings::default()))
+    }
+}
+
+impl<D, O, P> Calculable for Day<D, O, P>
+where
+    D: 'static + Debug,
+    O: 'static + Display,
+    P: 'static + Params + Clone,
+{
+    fn parse_with(&self, input: &str, settings: &ParamSettings) -> ParseResult<Box<dyn ParsedDay>> {
+        self.get_parse_func()(input, settings)
+    }
+    fn can_explain(&self) -> bool {
+        self.calc.explain.is_some()
+    }
+    fn get_parse_func(&self) -> ParseFunc {
         let parse = self.calc.parse;
         let part1 = self.calc.part1;
         let part2 = self.calc.part2;
-        Rc::new(move |input: &str| {
-            let input = parse(input)?;
-            Ok((
-                part1(&input).answer.to_string(),
-                part2(&input).answer.to_string(),
-            ))
+        let explain = self.calc.explain;
+        let params = self.params.clone();
+        Rc::new(move |input: &str, settings: &ParamSettings| {
+            let params = settings.apply(&params)?;
+            let input = parse_input(parse, input)?;
+            Ok(Box::new(Parsed {
+                input,
+                params,
+                part1,
+                part2,
+                explain,
+            }) as Box<dyn ParsedDay>)
         })
     }
 }
 
 pub trait DayTrait: Printable + Calculable + Send {}
 
-impl<D: 'static, O: 'static + std::fmt::Display> DayTrait for Day<D, O> {}
-
-pub fn get_days() -> BTreeMap<usize, Box<dyn DayTrait + 'static>> {
-    let mut days: BTreeMap<usize, Box<dyn DayTrait + 'static>> = BTreeMap::new();
-    days.insert(1, Box::new(day01::DAY));
-    days.insert(2, Box::new(day02::DAY));
-    days.insert(3, Box::new(day03::DAY));
-    days.insert(4, Box::new(day04::DAY));
-    days.insert(5, Box::new(day05::DAY));
-    days.insert(6, Box::new(day06::DAY));
-    days.insert(7, Box::new(day07::DAY));
-    days.insert(8, Box::new(day08::DAY));
-    days.insert(9, Box::new(day09::DAY));
-    days.insert(10, Box::new(day10::DAY));
-    days.insert(11, Box::new(day11::DAY));
-    days.insert(12, Box::new(day12::DAY));
-    days.insert(13, Box::new(day13::DAY));
-    days.insert(14, Box::new(day14::DAY));
-    days.insert(15, Box::new(day15::DAY));
-    days.insert(16, Box::new(day16::DAY));
-    days
-}
-
-pub fn get_input(day: usize) -> String {
-    match fs::read_to_string(format!("inputs/day{:02}.in.txt", day))
-        .or_else(|_| fs::read_to_string(format!("../inputs/day{:02}.in.txt", day)))
-    {
-        Err(e) => panic!("Err: {}, inputs/day{:02}.in.txt", e, day),
-        Ok(string) => string,
+impl<D, O, P> DayTrait for Day<D, O, P>
+where
+    D: 'static + Debug,
+    O: 'static + Display,
+    P: 'static + Params + Clone + Send,
+{
+}
+
+/// The day number in a module name like `day07`, checked at compile time
+const fn day_num(module: &str) -> usize {
+    let bytes = module.as_bytes();
+    assert!(
+        bytes.len() == 5 && bytes[0] == b'd' && bytes[1] == b'a' && bytes[2] == b'y',
+        "day modules must be named dayNN"
+    );
+    assert!(
+        bytes[3].is_ascii_digit() && bytes[4].is_ascii_digit(),
+        "day modules must be named dayNN"
+    );
+    ((bytes[3] - b'0') * 10 + (bytes[4] - b'0')) as usize
+}
+
+/// Fail to compile unless the days are numbered 1, 2, 3... in order
+// Only called from `const _`, which rustc does not count as a use
+#[allow(dead_code)]
+const fn check_day_nums(day_nums: &[usize]) {
+    let mut idx = 0;
+    while idx < day_nums.len() {
+        assert!(
+            day_nums[idx] == idx + 1,
+            "days must be unique, in order, and numbered contiguously from day01"
+        );
+        idx += 1;
+    }
+}
+
+/// Declare each day's module and register its `DAY` in [`get_days`].
+///
+/// Each module must be named `dayNN` and define a `DAY` constant. rustfmt does not follow
+/// modules declared inside a macro, so format them with `rustfmt advent2022-lib/src/day*.rs`.
+macro_rules! days {
+    ($($day:ident),+ $(,)?) => {
+        $(mod $day;)+
+
+        const _: () = check_day_nums(&[$(day_num(stringify!($day))),+]);
+
+        pub fn get_days() -> BTreeMap<usize, Box<dyn DayTrait + 'static>> {
+            let mut days: BTreeMap<usize, Box<dyn DayTrait + 'static>> = BTreeMap::new();
+            $(days.insert(day_num(stringify!($day)), Box::new($day::DAY));)+
+            days
+        }
+    };
+}
+
+days! {
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18,
+}
+
+/// Environment variable naming the directory that holds the `dayNN.in.txt` inputs
+pub const INPUTS_DIR_ENV: &str = "ADVENT2022_INPUTS";
+
+/// None of the places a day's input could be were readable
+#[derive(Debug)]
+pub struct InputError {
+    pub day: usize,
+    pub tried: Vec<(PathBuf, io::Error)>,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "could not read the input for day {}, tried:", self.day)?;
+        for (path, e) in &self.tried {
+            write!(f, "\n  {}: {}", path.display(), e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Read the input for a day from `inputs/` or `../inputs/`, or from the directory in
+/// [`INPUTS_DIR_ENV`] if it is set.
+pub fn get_input(day: usize) -> Result<String, InputError> {
+    get_input_from(day, None)
+}
+
+/// Read the input for a day from `inputs_dir`, falling back to [`get_input`]'s search when `None`
+pub fn get_input_from(day: usize, inputs_dir: Option<&Path>) -> Result<String, InputError> {
+    find_input(day, inputs_dir).map(|(_path, input)| input)
+}
+
+/// Read the input for a day, along with the path it was read from
+pub fn find_input(day: usize, inputs_dir: Option<&Path>) -> Result<(PathBuf, String), InputError> {
+    let env_dir = env::var_os(INPUTS_DIR_ENV).map(PathBuf::from);
+    let dirs = match inputs_dir.map(Path::to_path_buf).or(env_dir) {
+        Some(dir) => vec![dir],
+        None => vec![PathBuf::from("inputs"), PathBuf::from("../inputs")],
+    };
+    let mut tried = Vec::new();
+    for dir in dirs {
+        let path = dir.join(format!("day{:02}.in.txt", day));
+        match fs::read_to_string(&path) {
+            Ok(input) => return Ok((path, input)),
+            Err(e) => tried.push((path, e)),
+        }
     }
+    Err(InputError { day, tried })
 }
 
 #[macro_export]
@@ -0,0 +1,207 @@
+use std::fmt::Display;
+
+/// How a lit pixel is drawn, a full-width character so that images keep their proportions
+pub const LIT: char = '⬛';
+/// How a dark pixel is drawn, a full-width space as wide as [`LIT`]
+pub const DARK: char = '　';
+
+/// The height of the capital letters puzzles draw
+pub const LETTER_HEIGHT: usize = 6;
+/// The width of a letter, not counting the blank column that follows it
+pub const LETTER_WIDTH: usize = 4;
+const LETTER_SPACING: usize = LETTER_WIDTH + 1;
+
+/// Every letter the puzzles are known to draw, with `#` for lit pixels
+const ALPHABET: [(char, [&str; LETTER_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    // Y is five pixels wide, so only its first four columns are compared
+    ('Y', ["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// Letters are six pixels high, so no other height can be read
+    Height(usize),
+    /// The letter at this position, counting from 0, is not in the alphabet
+    UnknownLetter { position: usize, glyph: Image },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Height(height) => write!(
+                f,
+                "letters are {LETTER_HEIGHT} pixels high, but the image is {height}"
+            ),
+            Self::UnknownLetter { position, glyph } => {
+                write!(f, "unknown letter at position {position}:\n{glyph}")
+            },
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// A picture made of lit and dark pixels, like the one day 10's CRT draws
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    rows: Vec<Vec<bool>>,
+}
+
+impl Image {
+    /// An image from its rows of pixels, where `true` is lit
+    pub fn new(rows: Vec<Vec<bool>>) -> Self {
+        Self { rows }
+    }
+
+    /// An image from text rows, where `lit` marks the lit pixels
+    pub fn from_text<'a>(rows: impl IntoIterator<Item = &'a str>, lit: char) -> Self {
+        Self::new(
+            rows.into_iter()
+                .map(|row| row.chars().map(|c| c == lit).collect())
+                .collect(),
+        )
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or_default()
+    }
+
+    /// Whether a pixel is lit, where pixels outside the image are dark
+    pub fn is_lit(&self, x: usize, y: usize) -> bool {
+        self.rows
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The part of the image `width` pixels wide starting at column `x`
+    fn columns(&self, x: usize, width: usize) -> Self {
+        Self::new(
+            (0..self.height())
+                .map(|y| (x..x + width).map(|x| self.is_lit(x, y)).collect())
+                .collect(),
+        )
+    }
+
+    /// Read the capital letters drawn side by side across the image.
+    ///
+    /// Each letter is followed by a blank column, except that the image may end right after
+    /// the last one.
+    pub fn read_letters(&self) -> Result<String, OcrError> {
+        if self.height() != LETTER_HEIGHT {
+            return Err(OcrError::Height(self.height()));
+        }
+        (0..(self.width() + 1) / LETTER_SPACING)
+            .map(|position| {
+                let glyph = self.columns(position * LETTER_SPACING, LETTER_WIDTH);
+                ALPHABET
+                    .iter()
+                    .find(|(_, rows)| glyph == Image::from_text(*rows, '#'))
+                    .map(|(letter, _)| *letter)
+                    .ok_or(OcrError::UnknownLetter { position, glyph })
+            })
+            .collect()
+    }
+}
+
+impl Display for Image {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&lit| if lit { LIT } else { DARK })
+                    .collect()
+            })
+            .collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    /// Draw letters side by side the way the puzzles do, with a blank column after each
+    fn draw(letters: &str) -> Image {
+        let rows = (0..LETTER_HEIGHT).map(|y| {
+            letters
+                .chars()
+                .map(|letter| {
+                    let (_, rows) = ALPHABET.iter().find(|(c, _)| *c == letter).unwrap();
+                    format!("{}.", rows[y])
+                })
+                .collect::<String>()
+        });
+        Image::from_text(rows.collect::<Vec<_>>().iter().map(String::as_str), '#')
+    }
+
+    #[test]
+    fn test_read_alphabet() {
+        let alphabet: String = ALPHABET.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(draw(&alphabet).read_letters().unwrap(), alphabet);
+        for (letter, _) in ALPHABET {
+            assert_eq!(
+                draw(&letter.to_string()).read_letters().unwrap(),
+                letter.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_without_trailing_blank_column() {
+        // A 40 pixel wide CRT leaves no blank column after its eighth letter
+        let image = draw("EHZFZHCZ").columns(0, 39);
+        assert_eq!(image.width(), 39);
+        assert_eq!(image.read_letters().unwrap(), "EHZFZHCZ");
+    }
+
+    #[test]
+    fn test_read_five_pixel_y() {
+        let image = Image::from_text(["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."], '#');
+        assert_eq!(image.read_letters().unwrap(), "Y");
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let image = Image::from_text(["##..", "#..#"], '#');
+        assert_eq!(image.read_letters(), Err(OcrError::Height(2)));
+        let image = Image::from_text(["####"; LETTER_HEIGHT], '#');
+        assert!(matches!(
+            image.read_letters(),
+            Err(OcrError::UnknownLetter { position: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_display() {
+        let image = Image::from_text([".#", "#."], '#');
+        assert_eq!(image.to_string(), "　⬛\n⬛　");
+        assert_eq!(Image::from_text(image.to_string().lines(), LIT), image);
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt::{Debug, Display};
+
+/// A parameter of a day, as shown to someone who might want to change it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamInfo {
+    pub name: &'static str,
+    pub doc: &'static str,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// An override that is not `name=value`
+    Syntax(String),
+    Unknown {
+        name: String,
+        known: Vec<&'static str>,
+    },
+    Invalid {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Syntax(s) => write!(f, "expected name=value, found {s:?}"),
+            Self::Unknown { name, known } if known.is_empty() => {
+                write!(f, "unknown parameter {name:?}, this day has no parameters")
+            },
+            Self::Unknown { name, known } => write!(
+                f,
+                "unknown parameter {name:?}, expected one of {}",
+                known.join(", ")
+            ),
+            Self::Invalid {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid value {value:?} for {name}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Values a day's solver would otherwise hard-code, which can be set by name.
+///
+/// Implement it with [`params!`](crate::params!) rather than by hand.
+pub trait Params: Debug {
+    fn describe(&self) -> Vec<ParamInfo>;
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError>;
+}
+
+/// The parameters of a day that has none
+impl Params for () {
+    fn describe(&self) -> Vec<ParamInfo> {
+        Vec::new()
+    }
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), ParamError> {
+        Err(ParamError::Unknown {
+            name: name.to_owned(),
+            known: Vec::new(),
+        })
+    }
+}
+
+/// A day's parameters for real inputs, and the ones its example was written for
+#[derive(Debug, Clone)]
+pub struct DayParams<P> {
+    pub input: P,
+    pub example: P,
+}
+
+pub const NO_PARAMS: DayParams<()> = DayParams {
+    input: (),
+    example: (),
+};
+
+/// Which of a day's default parameters to start from, and which to override by name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParamSettings {
+    pub example: bool,
+    pub overrides: Vec<(String, String)>,
+}
+
+impl ParamSettings {
+    pub fn example() -> Self {
+        Self {
+            example: true,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Apply these settings to a day's parameters
+    pub fn apply<P: Params + Clone>(&self, params: &DayParams<P>) -> Result<P, ParamError> {
+        let mut applied = if self.example {
+            params.example.clone()
+        } else {
+            params.input.clone()
+        };
+        for (name, value) in &self.overrides {
+            applied.set(name, value)?;
+        }
+        Ok(applied)
+    }
+}
+
+/// Split an override like `row=10` into its name and value
+pub fn parse_override(s: &str) -> Result<(String, String), ParamError> {
+    s.split_once('=')
+        .filter(|(name, _)| !name.trim().is_empty())
+        .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+        .ok_or_else(|| ParamError::Syntax(s.to_owned()))
+}
+
+/// Declare a day's parameters struct and implement [`Params`] for it.
+///
+/// Each field's doc comment describes it wherever the parameters are listed, and its type
+/// must implement `FromStr` and `Display` so it can be set and shown by name.
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                #[doc = $doc:literal]
+                pub $field:ident: $ty:ty,
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $name {
+            $(
+                #[doc = $doc]
+                pub $field: $ty,
+            )+
+        }
+
+        impl $crate::params::Params for $name {
+            fn describe(&self) -> Vec<$crate::params::ParamInfo> {
+                vec![$($crate::params::ParamInfo {
+                    name: stringify!($field),
+                    doc: $doc.trim(),
+                    value: self.$field.to_string(),
+                }),+]
+            }
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), $crate::params::ParamError> {
+                match name {
+                    $(stringify!($field) => {
+                        self.$field = value.parse().map_err(|e| {
+                            $crate::params::ParamError::Invalid {
+                                name: name.to_owned(),
+                                value: value.to_owned(),
+                                reason: format!("{e}"),
+                            }
+                        })?
+                    },)+
+                    _ => {
+                        return Err($crate::params::ParamError::Unknown {
+                            name: name.to_owned(),
+                            known: vec![$(stringify!($field)),+],
+                        })
+                    },
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    params! {
+        pub struct TestParams {
+            /// How many
+            pub count: usize,
+            /// Where to start
+            pub start: isize,
+        }
+    }
+
+    const TEST_PARAMS: DayParams<TestParams> = DayParams {
+        input: TestParams {
+            count: 100,
+            start: 0,
+        },
+        example: TestParams { count: 3, start: 0 },
+    };
+
+    #[test]
+    fn test_params_apply() {
+        let settings = ParamSettings {
+            example: true,
+            overrides: vec![parse_override("start = -5").unwrap()],
+        };
+        let params = settings.apply(&TEST_PARAMS).unwrap();
+        assert_eq!((params.count, params.start), (3, -5));
+        assert_eq!(
+            params.describe(),
+            [
+                ParamInfo {
+                    name: "count",
+                    doc: "How many",
+                    value: String::from("3"),
+                },
+                ParamInfo {
+                    name: "start",
+                    doc: "Where to start",
+                    value: String::from("-5"),
+                },
+            ]
+        );
+        let params = ParamSettings::default().apply(&TEST_PARAMS).unwrap();
+        assert_eq!(params.count, 100);
+    }
+
+    #[test]
+    fn test_params_errors() {
+        assert_eq!(
+            parse_override("count"),
+            Err(ParamError::Syntax(String::from("count")))
+        );
+        let mut params = TEST_PARAMS.input.clone();
+        assert_eq!(
+            params.set("size", "1").unwrap_err().to_string(),
+            "unknown parameter \"size\", expected one of count, start"
+        );
+        assert!(matches!(
+            params.set("count", "-1"),
+            Err(ParamError::Invalid { .. })
+        ));
+        assert_eq!(
+            ().set("row", "10").unwrap_err().to_string(),
+            "unknown parameter \"row\", this day has no parameters"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use crate::{ParseError, ParseResult};
+
 const DELIMITERS: [&str; 6] = ["\n\n", "\n", ",", " ", ":", "-"];
 
 #[derive(Clone, Debug, PartialEq)]
@@ -68,6 +70,37 @@ pub fn read_delim<T: FromStr>(input: &str) -> Result<Parsed<T>, T::Err> {
     })
 }
 
+/// Parse each line of `input`, locating any error on the offending line.
+pub fn parse_lines<T>(input: &str) -> ParseResult<Vec<T>>
+where
+    T: FromStr,
+    ParseError: From<T::Err>,
+{
+    input
+        .lines()
+        .map(|line| {
+            line.parse()
+                .map_err(|e| ParseError::from(e).locate(input, line))
+        })
+        .collect()
+}
+
+/// Parse each blank-line separated block of `input`, locating any error within its block.
+pub fn parse_blocks<T>(input: &str) -> ParseResult<Vec<T>>
+where
+    T: FromStr,
+    ParseError: From<T::Err>,
+{
+    input
+        .split("\n\n")
+        .map(|block| {
+            block
+                .parse()
+                .map_err(|e| ParseError::from(e).locate(input, block))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs::read_to_string;
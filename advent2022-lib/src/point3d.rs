@@ -0,0 +1,158 @@
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// A position in 3D space, like a cube in a voxel grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point3 {
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The six points sharing a face with this one, leaving out any past the edge of the
+    /// coordinate space
+    pub fn neighbours(&self) -> impl Iterator<Item = Point3> {
+        let point = *self;
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .into_iter()
+        .filter_map(move |(dx, dy, dz)| {
+            Some(Self::new(
+                point.x.checked_add(dx)?,
+                point.y.checked_add(dy)?,
+                point.z.checked_add(dz)?,
+            ))
+        })
+    }
+}
+
+impl FromStr for Point3 {
+    type Err = ParseError;
+
+    /// Parse coordinates written as `x,y,z`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coords: Vec<&str> = s.split(',').collect();
+        let [x, y, z] = coords.as_slice() else {
+            return Err(ParseError::Str(format!(
+                "expected three coordinates like 1,2,3, found {}",
+                coords.len()
+            )));
+        };
+        let coord = |coord: &str| -> Result<isize, ParseError> {
+            coord
+                .trim()
+                .parse()
+                .map_err(|e| ParseError::from(e).locate(s, coord))
+        };
+        Ok(Self::new(coord(x)?, coord(y)?, coord(z)?))
+    }
+}
+
+/// The smallest box holding some points, including both corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Bounds {
+    /// The bounds of the points, or `None` if there are none
+    pub fn of<'p>(points: impl IntoIterator<Item = &'p Point3>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, point| {
+            Some(match bounds {
+                None => Self {
+                    min: *point,
+                    max: *point,
+                },
+                Some(Self { min, max }) => Self {
+                    min: Point3::new(min.x.min(point.x), min.y.min(point.y), min.z.min(point.z)),
+                    max: Point3::new(max.x.max(point.x), max.y.max(point.y), max.z.max(point.z)),
+                },
+            })
+        })
+    }
+
+    /// The bounds grown by `by` on every side, unless that passes the edge of the coordinate space
+    pub fn grow(&self, by: isize) -> Option<Self> {
+        Some(Self {
+            min: Point3::new(
+                self.min.x.checked_sub(by)?,
+                self.min.y.checked_sub(by)?,
+                self.min.z.checked_sub(by)?,
+            ),
+            max: Point3::new(
+                self.max.x.checked_add(by)?,
+                self.max.y.checked_add(by)?,
+                self.max.z.checked_add(by)?,
+            ),
+        })
+    }
+
+    pub fn contains(&self, point: &Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// How many points the bounds hold, unless there are too many to count
+    pub fn volume(&self) -> Option<usize> {
+        let len = |min: isize, max: isize| max.abs_diff(min).checked_add(1);
+        len(self.min.x, self.max.x)?
+            .checked_mul(len(self.min.y, self.max.y)?)?
+            .checked_mul(len(self.min.z, self.max.z)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1,-2,3".parse::<Point3>().unwrap(), Point3::new(1, -2, 3));
+        assert!("1,2".parse::<Point3>().is_err());
+        assert!("1,2,3,4".parse::<Point3>().is_err());
+        let error = "1,x,3".parse::<Point3>().unwrap_err();
+        assert_eq!(error.location().unwrap().columns, 2..3);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let point = Point3::new(1, 2, 3);
+        let neighbours: Vec<Point3> = point.neighbours().collect();
+        assert_eq!(neighbours.len(), 6);
+        assert!(neighbours.contains(&Point3::new(1, 2, 4)));
+        assert!(neighbours.contains(&Point3::new(0, 2, 3)));
+        let corner = Point3::new(isize::MAX, 0, isize::MIN);
+        assert_eq!(corner.neighbours().count(), 4);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let points = [Point3::new(1, 5, -2), Point3::new(3, 0, 4)];
+        let bounds = Bounds::of(&points).unwrap();
+        assert_eq!(bounds.min, Point3::new(1, 0, -2));
+        assert_eq!(bounds.max, Point3::new(3, 5, 4));
+        assert_eq!(bounds.volume(), Some(3 * 6 * 7));
+        assert!(bounds.contains(&Point3::new(2, 2, 2)));
+        assert!(!bounds.contains(&Point3::new(0, 2, 2)));
+        assert_eq!(bounds.grow(1).unwrap().volume(), Some(5 * 8 * 9));
+        assert_eq!(Bounds::of(&[]), None);
+        let edge = Bounds::of(&[Point3::new(isize::MAX, 0, 0)]).unwrap();
+        assert_eq!(edge.grow(1), None);
+    }
+}
@@ -1,31 +1,401 @@
 #[cfg(test)]
 mod tests {
     use std::fs::read_to_string;
+    use std::path::Path;
 
     use test_log::test;
 
-    use crate::get_days;
+    use crate::check::{answers_path, check_day, parse_answers, DayCheck};
+    use crate::params::{ParamError, ParamSettings};
+    use crate::{
+        example_file_stem, find_input, get_days, get_input_from, Location, ParseError, Part,
+        BOTH_PARTS,
+    };
+
+    /// Inputs that each day must reject with a `ParseError` rather than a panic
+    const MALFORMED: [(usize, &[&str]); 18] = [
+        (1, &["1000\n2000\nabc\n", "1000\n\n\n2000\n"]),
+        (2, &["A\n", "A Y Z\n", "D X\n", "A W\n"]),
+        (3, &["vJrwpWtwJgWrhcsFMMfFFhFp\nabc\n", "ab1d\n", "aébc\n"]),
+        (4, &["2-4\n", "2-4,6\n", "2-4,6-x\n", "2-4,,6-8\n"]),
+        (
+            5,
+            &[
+                "    [D]\n[N] [C]\n 1   2\n",
+                "[A]\n 1\n\nmove 1 from 1 to 2\n",
+                "[A]\n 1\n\nmove 1 from 0 to 1\n",
+                "[A]\n x\n\nmove 1 from 1 to 1\n",
+                "[A]\n 1\n\nmove one from 1 to 1\n",
+                "[A]\n\n\nmove 1 from 1 to 1\n",
+            ],
+        ),
+        (6, &["abc1def\n", "abcdef\nghijkl\n"]),
+        (
+            7,
+            &[
+                "ls\n",
+                "$ ls\n",
+                "$ cd /\n$ cd ..\n",
+                "$ cd /\n$ ls\nabc def\n",
+                "$ cd /\n$ rm -rf\n",
+                "$ cd /\n$ \n",
+            ],
+        ),
+        (8, &["30373\n25x12\n", "3037-3\n"]),
+        (9, &["R 4\nX 2\n", "R\n", "R -1\n", "R 1 2\n"]),
+        (10, &["noop\naddx\n", "addx 1 2\n", "mul 3\n", "noop 1\n"]),
+        (
+            11,
+            &[
+                "Monkey 0:\n  Starting items: 79\n",
+                "Monkey 1:\n  Starting items: 79, 98\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n",
+                "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * 19\n  Test: divisible by 0\n    If true: throw to monkey 1\n    If false: throw to monkey 1\n",
+                "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n",
+                "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 7\n    If false: throw to monkey 7\n",
+                "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old ^ 2\n  Test: divisible by 23\n    If true: throw to monkey 1\n    If false: throw to monkey 1\n",
+                "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old\n  Test: divisible by 23\n    If true: throw to monkey 1\n    If false: throw to monkey 1\n",
+            ],
+        ),
+        (12, &["Sabc\n", "SbcE\nSaaa\n", "Sab#E\n", "abcE\n", "SabEE\n"]),
+        (
+            13,
+            &[
+                "[1,[2]\n[1]\n",
+                "[1]]\n[1]\n",
+                "[1]\n",
+                "[1]\n[2]\n[3]\n",
+                "[1[2]]\n[1]\n",
+                "[1]\n[2,a]\n",
+                "[1]2\n[1]\n",
+                "[]\n\n\n[]\n",
+            ],
+        ),
+        (
+            14,
+            &[
+                "498,4 -> 498,6 -> 496,8\n",
+                "498,4 -> 498\n",
+                "a,4 -> 498,6\n",
+                "498,4 -> \n",
+                "0,0 -> 0,18446744073709551615\n",
+                "0,0 -> 0,100000000000\n",
+                "0,18446744073709551614\n",
+            ],
+        ),
+        (
+            15,
+            &[
+                "Sensor at x=2, y=18\n",
+                "Sensor at x=2: closest beacon is at x=-2, y=15\n",
+                "Sensor at x=2, y=18: closest beacon is at x=-2, y=99999999999999999999\n",
+            ],
+        ),
+        (
+            16,
+            &[
+                "Valve AA has flow rate=0; tunnels lead to valves BB\n",
+                "Valve BB has flow rate=0; tunnel leads to valve BB\n",
+                "Valve AAA has flow rate=0; tunnel leads to valve AA\n",
+                "Valve AA has flow rate=x; tunnel leads to valve AA\n",
+                "Valve AA has flow rate=0\n",
+                "Valve AA has flow rate=0; tunnel leads to valve BB\nValve BB has flow rate=5; tunnel leads to valve AA\nValve BB has flow rate=7; tunnel leads to valve AA\n",
+            ],
+        ),
+        (17, &["<<>x>\n", "<<>\n>><\n", "< >\n"]),
+        (18, &["1,2\n", "1,2,3,4\n", "1,a,3\n", "1,2,3\n\n4,5,6\n"]),
+    ];
+
+    /// Inputs that parse, but where the given part must fail with a `SolveError`, when run with
+    /// the parameters for examples
+    const UNSOLVABLE: [(usize, Part, &str); 17] = [
+        (5, Part::One, "[A]\n 1   2\n\nmove 2 from 1 to 2\n"),
+        (6, Part::One, "abcabc\n"),
+        (6, Part::Two, "abcdefghijklmabcdefghijklm\n"),
+        (7, Part::Two, "$ cd /\n$ ls\n80000000 a\n"),
+        (10, Part::One, "noop\naddx 3\n"),
+        (12, Part::One, "SazE\n"),
+        (13, Part::One, "[1]\n[1]\n"),
+        (14, Part::One, "498,4 -> 498,600\n"),
+        (14, Part::One, "500,0 -> 500,1\n"),
+        (14, Part::Two, "500,0 -> 500,1\n"),
+        (14, Part::One, "500,0\n499,1 -> 501,1\n"),
+        (14, Part::Two, "500,0\n499,1 -> 501,1\n"),
+        (
+            15,
+            Part::Two,
+            "Sensor at x=10, y=10: closest beacon is at x=10, y=40\n",
+        ),
+        (
+            15,
+            Part::One,
+            "Sensor at x=9223372036854775807, y=0: closest beacon is at x=9223372036854775807, y=20\n",
+        ),
+        (
+            15,
+            Part::Two,
+            "Sensor at x=9223372036854775807, y=0: closest beacon is at x=9223372036854775807, y=20\n",
+        ),
+        (17, Part::Two, "<\n"),
+        (18, Part::Two, "0,0,0\n1000,1000,1000\n"),
+    ];
+
+    #[test]
+    fn test_days_registered() {
+        let days = get_days();
+        assert_eq!(
+            days.keys().copied().collect::<Vec<_>>(),
+            (1..=days.len()).collect::<Vec<_>>()
+        );
+        for (day_num, day) in days {
+            assert!(
+                !day.get_examples().is_empty(),
+                "day {day_num} has no examples"
+            );
+            for (index, example) in day.get_examples().iter().enumerate() {
+                let stem = example_file_stem(day_num, index, example);
+                let input = read_to_string(format!("../examples/{stem}.in.txt"))
+                    .unwrap_or_else(|e| panic!("day {day_num} has no {stem}.in.txt: {e}"));
+                assert_eq!(
+                    example.input, input,
+                    "day {day_num} example {} does not use examples/{stem}.in.txt",
+                    example.name
+                );
+                assert_eq!(
+                    day.find_example(example.name).map(|(found, _)| found),
+                    Some(index),
+                    "day {day_num} has two examples named {}",
+                    example.name
+                );
+            }
+        }
+    }
 
     #[test]
     fn test_days_examples() {
         let days = get_days();
         for (day_num, day) in days {
-            if day_num == 10 {
-                continue;
+            for (index, example) in day.get_examples().iter().enumerate() {
+                let stem = example_file_stem(day_num, index, example);
+                // A newly scaffolded day starts with an empty example to fill in
+                if example.input.is_empty() {
+                    log::warn!("day {day_num} has an empty {stem}.in.txt");
+                    continue;
+                }
+                let expected = read_to_string(format!("../examples/{stem}.out.txt"))
+                    .unwrap_or_else(|e| panic!("day {day_num} has no {stem}.out.txt: {e}"));
+                let (expected1, expected2) = parse_answers(&expected);
+                assert!(
+                    expected1.is_some() || expected2.is_some(),
+                    "{stem}.out.txt expects nothing"
+                );
+                let run = day
+                    .run_with(example.input, &BOTH_PARTS, &example.settings(&[]))
+                    .unwrap();
+                for (part, expected) in BOTH_PARTS.into_iter().zip([expected1, expected2]) {
+                    // Some examples only give the answer to one part
+                    let Some(expected) = expected else { continue };
+                    let answer = run.part(part).unwrap().answer.as_ref().unwrap();
+                    // Printed as well as debugged, so multi-line answers like images line up
+                    assert_eq!(
+                        answer, &expected,
+                        "day {day_num} part {part} example {} mismatch, \
+                        expected\n{expected}\nbut found\n{answer}",
+                        example.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_days_parts_independent() {
+        let days = get_days();
+        for (day_num, day) in days {
+            for example in day.get_examples().iter().filter(|e| !e.input.is_empty()) {
+                let settings = example.settings(&[]);
+                let both = day.run_with(example.input, &BOTH_PARTS, &settings).unwrap();
+                for part in [Part::Two, Part::One] {
+                    let run = day.run_with(example.input, &[part], &settings).unwrap();
+                    assert_eq!(run.parts.len(), 1);
+                    assert_eq!(
+                        run.parts[0].answer.as_ref().ok(),
+                        both.part(part).unwrap().answer.as_ref().ok(),
+                        "day {day_num} part {part} differs when run alone on example {}",
+                        example.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_days_recorded_answers() {
+        for (day_num, day) in get_days() {
+            // Solving every real input is slow, so only check the days with recorded answers
+            match find_input(day_num, None) {
+                Ok((input_path, _)) if answers_path(&input_path).is_file() => {},
+                _ => continue,
+            }
+            match check_day(day_num, day.as_ref(), None) {
+                check @ DayCheck::Checked { .. } if check.failed() => {
+                    panic!("day {day_num} no longer matches its recorded answers: {check:?}")
+                },
+                check => log::info!("day {day_num}: {check:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_days_params() {
+        let days = get_days();
+        let example = days[&15].get_example();
+        let (_, row11) = days[&15].find_example("row11").unwrap();
+        assert_eq!(
+            days[&15].get_params(&row11.settings(&[])).unwrap()[0].value,
+            "11",
+            "an example's parameters apply on top of the day's example parameters"
+        );
+        let settings = row11.settings(&[(String::from("row"), String::from("10"))]);
+        let run = days[&15]
+            .run_with(row11.input, &[Part::One], &settings)
+            .unwrap();
+        assert_eq!(
+            run.parts[0].answer.as_ref().unwrap(),
+            "26",
+            "overrides apply on top of the example's parameters"
+        );
+        assert_eq!(
+            days[&9].find_example("2").map(|(_, example)| example.name),
+            Some("larger")
+        );
+        assert!(days[&9].find_example("3").is_none());
+
+        let settings = ParamSettings {
+            example: true,
+            overrides: vec![(String::from("rows"), String::from("11"))],
+        };
+        let error = days[&15]
+            .run_with(example, &[Part::One], &settings)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::Param(ParamError::Unknown { .. })
+        ));
+        let error = days[&1]
+            .run_with("1\n", &[Part::One], &settings)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::Param(ParamError::Unknown { .. })
+        ));
+    }
+
+    #[test]
+    fn test_days_parsed_run_explains() {
+        let days = get_days();
+        assert!(!days[&1].can_explain());
+        assert!(days[&16].can_explain());
+        let run = days[&16].get_parsed_run_func();
+        let (parsed, run) = run(
+            days[&16].get_example(),
+            &[Part::One],
+            &ParamSettings::example(),
+        )
+        .unwrap();
+        assert_eq!(run.parts[0].answer.as_ref().unwrap(), "1651");
+        assert!(matches!(parsed.explain(Part::One), Some(Ok(_))));
+    }
+
+    #[test]
+    fn test_get_input_missing() {
+        let error = get_input_from(1, Some(Path::new("no-such-inputs"))).unwrap_err();
+        assert_eq!(error.day, 1);
+        assert_eq!(error.tried.len(), 1);
+        let message = error.to_string();
+        assert!(
+            message.contains("no-such-inputs/day01.in.txt: "),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let days = get_days();
+        let error = days[&4].both("2-4,6-8\n2-x,4-5\n").unwrap_err();
+        assert!(matches!(error.reason(), ParseError::Int(_)));
+        assert_eq!(
+            error.location(),
+            Some(&Location {
+                line: 2,
+                columns: 2..3,
+                text: String::from("2-x,4-5"),
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            [
+                "invalid integer: invalid digit found in string",
+                " --> line 2, column 3",
+                "  |",
+                "2 | 2-x,4-5",
+                "  |   ^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_parse_error_location_nested() {
+        let days = get_days();
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+                     Sensor at x=9, y=16: closest beacon is at x=10, y=99999999999999999999\n";
+        let error = days[&15].both(input).unwrap_err();
+        let location = error.location().unwrap();
+        assert_eq!(location.line, 2);
+        assert_eq!(location.columns, 50..70);
+        assert_eq!(
+            &location.text[location.columns.clone()],
+            "99999999999999999999"
+        );
+    }
+
+    #[test]
+    fn test_days_malformed() {
+        let days = get_days();
+        assert_eq!(
+            days.keys().copied().collect::<Vec<_>>(),
+            MALFORMED
+                .iter()
+                .map(|(day_num, _)| *day_num)
+                .collect::<Vec<_>>(),
+            "every day needs a malformed input corpus"
+        );
+        for (day_num, inputs) in MALFORMED {
+            let day = &days[&day_num];
+            for input in ["", "\n  \n"].iter().chain(inputs.iter()) {
+                match day.both(input) {
+                    Ok(answers) => {
+                        panic!("day {day_num} accepted malformed input {input:?}: {answers:?}")
+                    },
+                    Err(e) => log::info!("day {day_num} rejected {input:?}: {e}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_days_unsolvable() {
+        let days = get_days();
+        for (day_num, part, input) in UNSOLVABLE {
+            let run = days[&day_num]
+                .run_with(input, &[part], &ParamSettings::example())
+                .unwrap_or_else(|e| panic!("day {day_num} rejected {input:?}: {e}"));
+            match &run.parts[0].answer {
+                Ok(answer) => {
+                    panic!("day {day_num} part {part} solved {input:?} with {answer}")
+                },
+                Err(e) => log::info!("day {day_num} part {part} failed on {input:?}: {e}"),
             }
-            let (part1, part2) = day.both(day.get_example()).unwrap();
-            let expected =
-                read_to_string(&format!("../examples/day{:02}.out.txt", day_num)).unwrap();
-            let expected_part1 = expected.lines().next().unwrap();
-            let expected_part2 = expected.lines().last().unwrap();
-            assert_eq!(
-                part1, expected_part1,
-                "day {day_num} part 1 example mismatch"
-            );
-            assert_eq!(
-                part2, expected_part2,
-                "day {day_num} part 2 example mismatch"
-            )
         }
     }
 }
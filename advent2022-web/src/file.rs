@@ -1,5 +1,3 @@
-use std::str::from_utf8;
-
 use gloo_file::callbacks::read_as_bytes;
 use gloo_file::File;
 use web_sys::HtmlInputElement;
@@ -36,7 +34,7 @@ pub fn FileUpload(props: &FileProps) -> Html {
             log::info!("loading file '{}'...", file_name);
             let file_load_callback = file_load_callback.clone();
             let reader = read_as_bytes(&gloo_file, move |blob_bytes| {
-                let read_string = from_utf8(&blob_bytes.unwrap()).unwrap().to_owned();
+                let read_string = String::from_utf8_lossy(&blob_bytes.unwrap()).into_owned();
                 file_load_callback.emit(read_string)
             });
             file_reader.set(Some((file_name, reader)))
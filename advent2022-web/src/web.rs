@@ -1,10 +1,23 @@
 // use std::collections::HashMap;
 
-use advent2022_lib::{DayTrait, ParseResult};
+use advent2022_lib::params::ParamSettings;
+use advent2022_lib::{DayTrait, Explanation, Part, BOTH_PARTS};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 use crate::file::FileUpload;
 
+/// The parts an uploaded file can be run for, as offered next to the upload button
+const UPLOAD_PARTS: [(&str, &[Part]); 3] = [
+    ("Both parts", &BOTH_PARTS),
+    ("Part 1", &[Part::One]),
+    ("Part 2", &[Part::Two]),
+];
+
+/// The messages to show for a run, and how each part's answer was found, for the days that can
+/// explain themselves
+type RunResults = (Vec<String>, Vec<(Part, Explanation)>);
+
 pub struct DayBox(pub Box<dyn DayTrait>);
 
 impl PartialEq for DayBox {
@@ -22,44 +35,129 @@ pub struct DayProps {
 #[function_component]
 pub fn DayView(props: &DayProps) -> Html {
     let text_format = props.day.0.get_display();
-    let part_calculate_func = props.day.0.get_both_func();
-    let get_messages = move |input: &str| -> Vec<String> {
-        let result: ParseResult<(String, String)> = part_calculate_func(input);
-        match result {
-            Err(_e) => {
-                log::error!("parsing error...");
-                vec!["Parsing error, please try again...".to_owned()]
+    let run_func = props.day.0.get_parsed_run_func();
+    let can_explain = props.day.0.can_explain();
+    let get_results = move |input: &str, parts: &[Part], settings: &ParamSettings| -> RunResults {
+        match run_func(input, parts, settings) {
+            Err(e) => {
+                log::error!("parsing error: {e}");
+                (
+                    vec![
+                        "Parsing error, please try again...".to_owned(),
+                        e.to_string(),
+                    ],
+                    Vec::new(),
+                )
             },
-            Ok(answer) => {
-                let part1 = format!("Part 1: {}", text_format.0.replace("{answer}", &answer.0));
-                let part2 = format!("Part 2: {}", text_format.1.replace("{answer}", &answer.1));
-                log::info!("{}", part1);
-                log::info!("{}", part2);
-                vec![part1, part2]
+            Ok((parsed, run)) => {
+                let mut messages = vec![format!("Parsed in {:?}", run.parse_duration)];
+                for part_run in &run.parts {
+                    let display = match part_run.part {
+                        Part::One => text_format.0,
+                        Part::Two => text_format.1,
+                    };
+                    let message = match &part_run.answer {
+                        Ok(answer) => format!(
+                            "Part {}: {} ({:?})",
+                            part_run.part,
+                            display.replace("{answer}", answer),
+                            part_run.duration
+                        ),
+                        Err(e) => format!(
+                            "Part {}: Unsolved, {e} ({:?})",
+                            part_run.part, part_run.duration
+                        ),
+                    };
+                    log::info!("{}", message);
+                    messages.push(message);
+                }
+                messages.push(format!("Total: {:?}", run.total_duration()));
+                let explanations = if can_explain {
+                    parts
+                        .iter()
+                        .filter_map(|&part| Some((part, parsed.explain(part)?.ok()?)))
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                (messages, explanations)
             },
         }
     };
 
     let messages = use_state(|| Vec::new());
+    let explanations = use_state(Vec::<(Part, Explanation)>::new);
+    // Parameters changed from the page, applied on top of the example's or the input's defaults
+    let overrides = use_state(Vec::<(String, String)>::new);
+
+    let on_param_change = |name: &'static str| {
+        let overrides = overrides.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let value = input.value().trim().to_owned();
+            let mut changed: Vec<_> = overrides
+                .iter()
+                .filter(|(other, _)| other != name)
+                .cloned()
+                .collect();
+            if !value.is_empty() {
+                changed.push((name.to_owned(), value));
+            }
+            overrides.set(changed);
+        })
+    };
+
+    let examples = props.day.0.get_examples();
+    let example_index = use_state(|| 0);
+
+    let on_example_select = {
+        let example_index = example_index.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            example_index.set(select.value().parse().unwrap_or(0));
+        })
+    };
 
-    let on_run_example = {
-        let example = props.day.0.get_example();
+    let on_run_example = |parts: &'static [Part]| {
+        let example = &examples[*example_index];
         let messages = messages.clone();
-        let get_messages = get_messages.clone();
+        let explanations = explanations.clone();
+        let get_results = get_results.clone();
+        let settings = example.settings(&overrides);
         Callback::from(move |_| {
-            log::info!("Running Example");
-            messages.set(get_messages(example));
+            log::info!("Running Example {}", example.name);
+            let (example_messages, example_explanations) =
+                get_results(example.input, parts, &settings);
+            messages.set(example_messages);
+            explanations.set(example_explanations);
+        })
+    };
+
+    let upload_parts_index = use_state(|| 0);
+
+    let on_upload_parts_select = {
+        let upload_parts_index = upload_parts_index.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            upload_parts_index.set(select.value().parse().unwrap_or(0));
         })
     };
 
     let on_file_load = {
+        let parts = UPLOAD_PARTS[*upload_parts_index].1;
         let messages_state = messages.clone();
-        let get_messages = get_messages.clone();
+        let explanations_state = explanations.clone();
+        let get_results = get_results.clone();
+        let settings = ParamSettings {
+            example: false,
+            overrides: (*overrides).clone(),
+        };
         Callback::from(move |input: String| {
             log::info!("Running Loaded File");
-            let mut messages = get_messages(&input);
+            let (mut messages, explanations) = get_results(&input, parts, &settings);
             messages.insert(0, String::from("From Upload"));
             messages_state.set(messages);
+            explanations_state.set(explanations);
         })
     };
 
@@ -72,7 +170,12 @@ pub fn DayView(props: &DayProps) -> Html {
         })
     };
 
-    let example = props.day.0.get_example();
+    let example = examples[*example_index].input;
+    let params = props
+        .day
+        .0
+        .get_params(&ParamSettings::default())
+        .unwrap_or_default();
     html! {
         <section class={if props.day_num & 1 != 0 { "day-odd" } else { "day-even" }}>
             <div class="row">
@@ -82,8 +185,34 @@ pub fn DayView(props: &DayProps) -> Html {
             </div>
             <div class="row row-reverse">
                 <FileUpload day_num={props.day_num} file_load_callback={on_file_load} />
+                <div class="row-item day-upload-parts">
+                    <select onchange={on_upload_parts_select}>
+                        {
+                            for UPLOAD_PARTS.iter().enumerate().map(|(index, (label, _))| html! {
+                                <option value={index.to_string()} selected={index == *upload_parts_index}>{*label}</option>
+                            })
+                        }
+                    </select>
+                </div>
                 <div class="row-item day-run">
-                    <button type="button" onclick={on_run_example}>{ "▶ Run..." }</button>
+                    {
+                        if examples.len() > 1 {
+                            html! {
+                                <select onchange={on_example_select}>
+                                    {
+                                        for examples.iter().enumerate().map(|(index, example)| html! {
+                                            <option value={index.to_string()} selected={index == *example_index}>{example.name}</option>
+                                        })
+                                    }
+                                </select>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    <button type="button" onclick={on_run_example(&BOTH_PARTS)}>{ "▶ Run..." }</button>
+                    <button type="button" onclick={on_run_example(&[Part::One])}>{ "▶ Part 1" }</button>
+                    <button type="button" onclick={on_run_example(&[Part::Two])}>{ "▶ Part 2" }</button>
                 </div>
                 <div class="row-item day-collapse">
                     <h5 class={if example.lines().count() > 1 {"button"} else {"button disabled"}} onclick={on_collapse}>
@@ -101,6 +230,24 @@ pub fn DayView(props: &DayProps) -> Html {
                     </h5>
                 </div>
             </div>
+            {
+                if params.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <div class="row day-params">
+                            {
+                                for params.iter().map(|param| html! {
+                                    <label class="row-item" title={param.doc}>
+                                        {param.name}{" "}
+                                        <input type="text" placeholder={param.value.clone()} onchange={on_param_change(param.name)} />
+                                    </label>
+                                })
+                            }
+                        </div>
+                    }
+                }
+            }
                 {
                     if *show_input {
                         html! {
@@ -116,11 +263,34 @@ pub fn DayView(props: &DayProps) -> Html {
                 }
                 {
                     for messages.iter().map(|message| {
-                        html! {
-                            <p>{message}</p>
+                        if message.lines().count() > 1 {
+                            html! {
+                                <pre>{message}</pre>
+                            }
+                        } else {
+                            html! {
+                                <p>{message}</p>
+                            }
                         }
                     })
                 }
+                {
+                    for explanations.iter().map(|(part, explanation)| html! {
+                        <table class="explanation">
+                            <caption>{format!("Part {part} explained")}</caption>
+                            <thead>
+                                <tr>{ for explanation.headings.iter().map(|heading| html! { <th>{heading}</th> }) }</tr>
+                            </thead>
+                            <tbody>
+                                {
+                                    for explanation.rows.iter().map(|row| html! {
+                                        <tr>{ for row.iter().map(|cell| html! { <td>{cell}</td> }) }</tr>
+                                    })
+                                }
+                            </tbody>
+                        </table>
+                    })
+                }
         </section>
     }
 }
@@ -0,0 +1,19 @@
+#!/bin/bash
+# Seed each fuzz target's corpus with the day's examples and input, then run it with
+#   cargo fuzz run dayNN
+# A crash saved in artifacts/dayNN/ is replayed by the library's tests once it is fixed.
+
+set -e
+set -u
+set -o pipefail
+
+cd "$(dirname "$0")"
+for target in fuzz_targets/day*.rs; do
+  day="$(basename "$target" .rs)"
+  mkdir -p "corpus/$day"
+  for seed in ../examples/"$day"*.in.txt ../inputs/"$day".in.txt; do
+    if [ -s "$seed" ]; then
+      cp "$seed" "corpus/$day/"
+    fi
+  done
+done